@@ -0,0 +1,2300 @@
+//! Derive macros for the [`env-extract`](https://docs.rs/env-extract) crate.
+//!
+//! This crate is not meant to be used directly. Depend on `env-extract` instead, which re-exports
+//! the `ConfigStruct` and `EnvVar` derives alongside the types the generated code relies on.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Delimiter, Spacing, TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse_macro_input, Attribute, DeriveInput, Lit, Meta, MetaNameValue};
+
+/// Shown in place of the values of `#[secret]` fields.
+const REDACTED: &str = "<redacted>";
+
+/// This proc macro is applied to enums and implements the `EnvVar` trait, which provides a `.get()`
+/// method to retrieve a value of type `T` from an environment variable. The `.get_result()` method
+/// returns an `env_extract::EnvVarError` instead, which distinguishes a missing variable from an
+/// unrecognised value and lists the accepted values. `get_from` and `try_get_from` do the same with
+/// any `env_extract::Source` instead of the process environment.
+///
+/// The macro parses the environment variable to the enum type and requires one of the following:
+/// - A variant called "Invalid", which will be returned if the environment variable does not match
+///   any of the variants.
+/// - A variant marked with `#[default]`, which will be returned if the environment variable does
+///   not match any of the variants.
+/// - The enum to be marked with `#[panic_on_invalid]`, which will panic if the environment variable
+///   does not match any of the variants.
+///
+/// The macro supports the following attributes on the enum itself:
+/// - `#[env_var = "FOO"]`: Set a custom environment variable name to search for. Defaults to the
+///   name of the enum in uppercase.
+/// - `#[var_name(primary = "FOO", aliases = ["OLD_FOO"], deprecated)]`: Falls back to the aliases
+///   in order when `FOO` is not set. With `deprecated`, reading an alias is reported through
+///   `env_extract::set_deprecation_hook`.
+/// - `#[panic_on_invalid]`: Panics if a valid variant is not found.
+/// - `#[case(convert = "[uppercase|lowercase|exact|any]")]`: Converts all environment variable
+///   values to a specific case before comparing them to map the valid variant. This attribute is
+///   overwritten if the variant also contains this attribute.
+///
+/// The macro also supports the following attributes on the enum variants:
+/// - `#[case = "[uppercase|lowercase|exact|any]"]`: Specifies case conversion for the annotated
+///   enum variant. The `uppercase` and `lowercase` options convert the environment variable value
+///   to uppercase or lowercase before comparing it to the variant name. The `exact` option compares
+///   the environment variable value to the variant name without any case conversion. The `any`
+///   option converts both the environment variable value and the variant name to lowercase before
+///   comparing them.
+/// - `#[default]`: Specifies the default enum variant.
+/// - `#[ignore_variant]`: Ignores the annotated enum variant when checking for a match.
+///
+/// Example usage:
+///
+/// ```rust
+/// use env_extract::EnvVar;
+///
+/// #[derive(EnvVar)]
+/// #[var_name = "DATABASE_TYPE"]
+/// #[case(convert = "uppercase")]
+/// enum DatabaseType {
+///     #[case(convert = "lowercase")]
+///     Postgres,
+///     Mysql,
+///
+///     #[default]
+///     Sqlite,
+/// }
+///
+/// fn main() {
+///     std::env::set_var("DATABASE_TYPE", "MYSQL");
+///
+///     let database_type = DatabaseType::get();
+///     assert!(matches!(database_type, DatabaseType::Mysql));
+/// }
+/// ```
+///
+/// In the example above, the `EnvVar` trait is implemented for the `DatabaseType` enum, allowing
+/// the retrieval of a value from the "DATABASE_TYPE" environment variable. The enum variants are
+/// compared to the environment variable value after applying case conversions specified by the
+/// `#[case]` attributes. The `Mysql` variant is matched since the environment variable value is
+/// converted to uppercase and the variant name to lowercase, resulting in a match.
+///
+/// `json_schema()` describes the variable as a JSON Schema listing the accepted values, and the
+/// generated `VARIANTS` constant lists each variant with the value that selects it.
+#[proc_macro_derive(
+    EnvVar,
+    attributes(case, var_name, default, panic_on_invalid, ignore_variant)
+)]
+pub fn enum_from_env(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_enum_from_env(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_enum_from_env(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let enum_name = &input.ident;
+
+    let var_name = get_var_name(&input.attrs)?;
+    let (aliases, deprecated) = match &var_name {
+        Some(v) => (v.aliases.clone(), v.deprecated),
+        None => (Vec::new(), false),
+    };
+    let var_name_to_check_for = match var_name.and_then(|v| v.primary) {
+        Some(v) => v,
+        None => enum_name.to_string().to_uppercase(),
+    };
+
+    let variants = match input.data {
+        syn::Data::Enum(ref variants) => &variants.variants,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                enum_name,
+                "EnvVar can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut invalid_type: Option<&syn::Ident> = None;
+
+    for variant in variants {
+        if &variant.ident.to_token_stream().to_string() == "Invalid" {
+            invalid_type = Some(&variant.ident);
+        };
+    }
+
+    let mut default_value: Option<&syn::Ident> = None;
+
+    let panic_on_invalid = input.attrs.iter().any(|attr| {
+        if let Ok(Meta::Path(path)) = attr.parse_meta() {
+            path.is_ident("panic_on_invalid")
+        } else {
+            false
+        }
+    });
+
+    let default_case = get_case_conversion(&input.attrs)?;
+
+    let mut check_variants = Vec::new();
+    let mut accepted_values = Vec::new();
+    let mut variant_infos = Vec::new();
+    for variant in variants {
+        if let syn::Fields::Unit = variant.fields {
+            let ignore_variant = get_empty_path_attribute(&variant.attrs, "ignore_variant");
+
+            if ignore_variant {
+                continue;
+            }
+
+            let variant_name = &variant.ident;
+
+            let case = get_case_conversion(&variant.attrs)?;
+            if default_value.is_none() && get_empty_path_attribute(&variant.attrs, "default") {
+                default_value = Some(variant_name);
+            }
+
+            let accepted_value = if case.1 {
+                convert_case(&variant_name.to_string(), &case.0)
+            } else {
+                convert_case(&variant_name.to_string(), &default_case.0)
+            };
+
+            let var_case_conversion = if let CaseConversion::Any = case.0 {
+                quote! { .to_lowercase() }
+            } else {
+                quote! {}
+            };
+
+            check_variants.push(quote! {
+                if value #var_case_conversion == #accepted_value {
+                    return Ok(#enum_name::#variant_name);
+                }
+            });
+            variant_infos.push((
+                variant_name,
+                accepted_value.clone(),
+                matches!(case.0, CaseConversion::Any),
+            ));
+            accepted_values.push(accepted_value);
+        }
+    }
+
+    if invalid_type.is_none() && default_value.is_none() && !panic_on_invalid {
+        return Err(syn::Error::new_spanned(
+            enum_name,
+            "EnvVar enum must have an `Invalid` variant, a variant marked with #[default], or be marked with #[panic_on_invalid]",
+        ));
+    }
+
+    let invalid_value = match default_value {
+        _ if panic_on_invalid => quote! { panic!("Invalid environment variable value") },
+        Some(v) => quote! { #enum_name::#v },
+        None => quote! { #enum_name::Invalid },
+    };
+
+    let fallback = match default_value {
+        _ if panic_on_invalid => None,
+        Some(v) => Some(v),
+        None => invalid_type,
+    };
+    let variant_infos =
+        variant_infos
+            .into_iter()
+            .map(|(variant_name, accepted_value, any_case)| {
+                let is_fallback = fallback == Some(variant_name);
+                quote! {
+                    ::env_extract::VariantInfo {
+                        name: stringify!(#variant_name),
+                        value: #accepted_value,
+                        any_case: #any_case,
+                        fallback: #is_fallback,
+                    }
+                }
+            });
+
+    let handle_error = if panic_on_invalid {
+        quote! { Err(e) => panic!("{}", e) }
+    } else {
+        quote! { Err(..) => #invalid_value }
+    };
+
+    let lookup_value = if aliases.is_empty() {
+        quote! {
+            match source.get(#var_name_to_check_for) {
+                Some(v) => (#var_name_to_check_for, v),
+                None => {
+                    return Err(::env_extract::EnvVarError::Missing {
+                        var_name: #var_name_to_check_for.to_string(),
+                    })
+                }
+            }
+        }
+    } else {
+        quote! {
+            match ::env_extract::__private::lookup_aliased(
+                source,
+                #var_name_to_check_for.to_string(),
+                Self::VAR_ALIASES,
+                #deprecated,
+                false,
+            ) {
+                (var_name, Ok(Some((v, _)))) => (var_name, v),
+                (var_name, _) => return Err(::env_extract::EnvVarError::Missing { var_name }),
+            }
+        }
+    };
+    let var_aliases = if aliases.is_empty() {
+        quote! {}
+    } else {
+        quote! { const VAR_ALIASES: &'static [&'static str] = &[#(#aliases),*]; }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics ::env_extract::EnvVar for #enum_name #ty_generics #where_clause {
+            const VAR_NAME: &'static str = #var_name_to_check_for;
+            #var_aliases
+
+            const VARIANTS: &'static [::env_extract::VariantInfo] =
+                &[#(#variant_infos),*];
+
+            fn get_from(source: &dyn ::env_extract::Source) -> Self {
+                match Self::try_get_from(source) {
+                    Ok(v) => v,
+                    #handle_error,
+                }
+            }
+
+            fn try_get_from(
+                source: &dyn ::env_extract::Source,
+            ) -> Result<Self, ::env_extract::EnvVarError> {
+                let (var_name, value) = #lookup_value;
+
+                #(#check_variants)*
+
+                Err(::env_extract::EnvVarError::Invalid {
+                    var_name: var_name.to_string(),
+                    value,
+                    expected: &[#(#accepted_values),*],
+                })
+            }
+
+            fn fallback() -> Self {
+                #invalid_value
+            }
+        }
+    };
+
+    Ok(expanded)
+}
+
+enum CaseConversion {
+    Uppercase,
+    Lowercase,
+    Exact,
+    Any,
+}
+
+fn convert_case(value: &str, case: &CaseConversion) -> String {
+    match case {
+        CaseConversion::Uppercase => value.to_uppercase(),
+        CaseConversion::Lowercase | CaseConversion::Any => value.to_lowercase(),
+        CaseConversion::Exact => value.to_string(),
+    }
+}
+
+/// The names given with `#[var_name = "..."]` or
+/// `#[var_name(primary = "...", aliases = ["..."], deprecated)]`.
+struct VarName {
+    /// The name to read, or `None` to use the name derived from the field or enum.
+    primary: Option<String>,
+    aliases: Vec<String>,
+    deprecated: bool,
+}
+
+fn get_var_name(attr: &[Attribute]) -> syn::Result<Option<VarName>> {
+    for attr in attr {
+        if !attr.path.is_ident("var_name") {
+            continue;
+        }
+        if let Ok(Meta::NameValue(meta_value)) = attr.parse_meta() {
+            match meta_value.lit {
+                syn::Lit::Str(ref s) => {
+                    return Ok(Some(VarName {
+                        primary: Some(s.value()),
+                        aliases: Vec::new(),
+                        deprecated: false,
+                    }))
+                }
+                ref lit => {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "var_name must be a string literal, e.g. #[var_name = \"FOO\"]",
+                    ))
+                }
+            }
+        }
+        // `parse_meta` cannot read the `aliases = [...]` array, so the list form is parsed by hand.
+        return attr.parse_args_with(parse_var_name_list).map(Some);
+    }
+    Ok(None)
+}
+
+fn parse_var_name_list(input: syn::parse::ParseStream) -> syn::Result<VarName> {
+    let mut var_name = VarName {
+        primary: None,
+        aliases: Vec::new(),
+        deprecated: false,
+    };
+    let mut deprecated_span = None;
+
+    while !input.is_empty() {
+        let key: syn::Ident = input.parse()?;
+        match key.to_string().as_str() {
+            "primary" => {
+                input.parse::<syn::Token![=]>()?;
+                var_name.primary = Some(input.parse::<syn::LitStr>()?.value());
+            }
+            "aliases" => {
+                input.parse::<syn::Token![=]>()?;
+                let content;
+                syn::bracketed!(content in input);
+                let aliases =
+                    syn::punctuated::Punctuated::<syn::LitStr, syn::Token![,]>::parse_terminated(
+                        &content,
+                    )?;
+                var_name
+                    .aliases
+                    .extend(aliases.iter().map(syn::LitStr::value));
+            }
+            "deprecated" => {
+                var_name.deprecated = true;
+                deprecated_span = Some(key.span());
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "unknown var_name option, expected `primary = \"...\"`, `aliases = [\"...\"]` or `deprecated`",
+                ))
+            }
+        }
+        if !input.is_empty() {
+            input.parse::<syn::Token![,]>()?;
+        }
+    }
+
+    if let (Some(span), true) = (deprecated_span, var_name.aliases.is_empty()) {
+        return Err(syn::Error::new(
+            span,
+            "`deprecated` marks the aliases as deprecated, but none are given",
+        ));
+    }
+    Ok(var_name)
+}
+
+fn get_env_prefix(attrs: &[Attribute]) -> syn::Result<String> {
+    for attr in attrs {
+        if attr.path.is_ident("env_prefix") {
+            return match attr.parse_meta()? {
+                Meta::NameValue(MetaNameValue {
+                    lit: Lit::Str(value),
+                    ..
+                }) => Ok(value.value()),
+                other => Err(syn::Error::new_spanned(
+                    other,
+                    "expected a string prefix, e.g. #[env_prefix = \"APP_\"]",
+                )),
+            };
+        }
+    }
+    Ok(String::new())
+}
+
+fn get_case_conversion(attrs: &[Attribute]) -> syn::Result<(CaseConversion, bool)> {
+    for attr in attrs {
+        if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
+            if meta_list.path.is_ident("case") {
+                for nested_meta in meta_list.nested {
+                    if let syn::NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                        path,
+                        lit: Lit::Str(value),
+                        ..
+                    })) = nested_meta
+                    {
+                        if path.is_ident("convert") {
+                            match value.value().as_str() {
+                                "uppercase" => return Ok((CaseConversion::Uppercase, true)),
+                                "lowercase" => return Ok((CaseConversion::Lowercase, true)),
+                                "exact" => return Ok((CaseConversion::Exact, true)),
+                                "any" => return Ok((CaseConversion::Any, true)),
+                                _ => {
+                                    return Err(syn::Error::new_spanned(
+                                        value,
+                                        "invalid case conversion, expected one of \"uppercase\", \"lowercase\", \"exact\" or \"any\"",
+                                    ))
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok((CaseConversion::Exact, false))
+}
+
+fn get_empty_path_attribute(attrs: &[Attribute], path: &str) -> bool {
+    for attr in attrs {
+        if let Ok(Meta::Path(meta_path)) = attr.parse_meta() {
+            if meta_path.is_ident(path) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn get_default_value(attrs: &[Attribute]) -> syn::Result<Option<String>> {
+    for attr in attrs {
+        if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
+            if meta_list.path.is_ident("default") {
+                if let Some(nested_meta) = meta_list.nested.first() {
+                    return match nested_meta {
+                        syn::NestedMeta::Lit(Lit::Str(value)) => Ok(Some(value.value())),
+                        syn::NestedMeta::Lit(Lit::Int(value)) => {
+                            Ok(Some(value.base10_digits().to_string()))
+                        }
+                        syn::NestedMeta::Lit(Lit::Float(value)) => {
+                            Ok(Some(value.base10_digits().to_string()))
+                        }
+                        syn::NestedMeta::Lit(Lit::Bool(value)) => Ok(Some(value.value.to_string())),
+                        other => Err(syn::Error::new_spanned(
+                            other,
+                            "default must be a literal, e.g. #[default(\"localhost\")]",
+                        )),
+                    };
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+#[derive(Debug)]
+enum PrimitiveType {
+    String,
+    Number,
+    Bool,
+    Bytes,
+    Duration,
+    ImplementedEnum,
+    Optional(Box<PrimitiveType>),
+    List(Box<PrimitiveType>),
+    Secret(Box<PrimitiveType>),
+    FromStr,
+    Nested,
+}
+
+fn is_list_type(field_type: &PrimitiveType) -> bool {
+    match field_type {
+        PrimitiveType::List(..) => true,
+        PrimitiveType::Secret(inner) => is_list_type(inner),
+        _ => false,
+    }
+}
+
+fn is_duration_type(field_type: &PrimitiveType) -> bool {
+    match field_type {
+        PrimitiveType::Duration => true,
+        PrimitiveType::List(inner) => is_duration_type(inner),
+        _ => false,
+    }
+}
+
+fn is_bytes_type(field_type: &PrimitiveType) -> bool {
+    match field_type {
+        PrimitiveType::Bytes => true,
+        PrimitiveType::List(inner) | PrimitiveType::Secret(inner) => is_bytes_type(inner),
+        _ => false,
+    }
+}
+
+fn is_secret_type(field_type: &PrimitiveType) -> bool {
+    matches!(field_type, PrimitiveType::Secret(..))
+}
+
+/// Whether the type `ty`, which `field_type` classifies, implements `Zeroize` and so can be held
+/// in a `Secret`.
+fn is_zeroizable(field_type: &PrimitiveType, ty: &syn::Type) -> bool {
+    match field_type {
+        PrimitiveType::String
+        | PrimitiveType::Number
+        | PrimitiveType::Bool
+        | PrimitiveType::Bytes => true,
+        PrimitiveType::List(item) => {
+            get_generic_argument(ty, "Vec").is_some_and(|item_ty| is_zeroizable(item, item_ty))
+        }
+        _ => false,
+    }
+}
+
+/// Returns `T` when `ty` is `Secret<T>` or `env_extract::Secret<T>`.
+fn get_secret_argument(ty: &syn::Type) -> Option<&syn::Type> {
+    match ty {
+        syn::Type::Path(type_path) if is_path_to(type_path, &["Secret", "env_extract::Secret"]) => {
+            get_generic_argument(ty, "Secret")
+        }
+        _ => None,
+    }
+}
+
+fn get_list_element(ty: &syn::Type) -> Option<&syn::Type> {
+    ["Vec", "HashSet", "BTreeSet"]
+        .iter()
+        .find_map(|wrapper| get_generic_argument(ty, wrapper))
+}
+
+struct NestedOptions {
+    prefix: String,
+    separator: String,
+}
+
+fn get_nested_options(
+    attrs: &[Attribute],
+    field_ident: &syn::Ident,
+) -> syn::Result<Option<NestedOptions>> {
+    let attr = match attrs.iter().find(|attr| attr.path.is_ident("nested")) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+
+    let mut options = NestedOptions {
+        prefix: field_ident.to_string().to_uppercase(),
+        separator: "_".to_string(),
+    };
+
+    let meta_list = match attr.parse_meta()? {
+        Meta::List(meta_list) => meta_list,
+        Meta::Path(..) => return Ok(Some(options)),
+        other => {
+            return Err(syn::Error::new_spanned(
+                other,
+                "expected #[nested] or #[nested(prefix = \"...\", separator = \"...\")]",
+            ))
+        }
+    };
+
+    for nested_meta in meta_list.nested {
+        match nested_meta {
+            syn::NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(value),
+                ..
+            })) if path.is_ident("prefix") => options.prefix = value.value(),
+            syn::NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(value),
+                ..
+            })) if path.is_ident("separator") => options.separator = value.value(),
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    "unknown nested option, expected `prefix = \"...\"` or `separator = \"...\"`",
+                ))
+            }
+        }
+    }
+
+    Ok(Some(options))
+}
+
+struct ListOptions {
+    attribute: Option<Attribute>,
+    separator: String,
+    trim: bool,
+    skip_empty: bool,
+}
+
+fn get_list_options(attrs: &[Attribute]) -> syn::Result<ListOptions> {
+    let mut options = ListOptions {
+        attribute: None,
+        separator: ",".to_string(),
+        trim: true,
+        skip_empty: true,
+    };
+
+    for attr in attrs {
+        if !attr.path.is_ident("list") {
+            continue;
+        }
+        options.attribute = Some(attr.clone());
+
+        let meta_list = match attr.parse_meta()? {
+            Meta::List(meta_list) => meta_list,
+            Meta::Path(..) => continue,
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    "expected #[list(separator = \",\", trim = true, skip_empty = true)]",
+                ))
+            }
+        };
+
+        for nested_meta in meta_list.nested {
+            match nested_meta {
+                syn::NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(value),
+                    ..
+                })) if path.is_ident("separator") => {
+                    if value.value().is_empty() {
+                        return Err(syn::Error::new_spanned(value, "separator cannot be empty"));
+                    }
+                    options.separator = value.value();
+                }
+                syn::NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Bool(value),
+                    ..
+                })) if path.is_ident("trim") => options.trim = value.value,
+                syn::NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Bool(value),
+                    ..
+                })) if path.is_ident("skip_empty") => options.skip_empty = value.value,
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        "unknown list option, expected `separator = \"...\"`, `trim = bool` or `skip_empty = bool`",
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(options)
+}
+
+const DURATION_UNITS: &[&str] = &["ns", "us", "µs", "ms", "s", "m", "h", "d"];
+
+/// Returns the `#[duration(unit = "...")]` attribute, if there is one, and the unit bare numbers
+/// are read in, which defaults to seconds.
+fn get_duration_unit(attrs: &[Attribute]) -> syn::Result<(Option<&Attribute>, String)> {
+    let attr = match attrs.iter().find(|attr| attr.path.is_ident("duration")) {
+        Some(attr) => attr,
+        None => return Ok((None, "s".to_string())),
+    };
+
+    if let Meta::List(meta_list) = attr.parse_meta()? {
+        if let Some(syn::NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+            path,
+            lit: Lit::Str(value),
+            ..
+        }))) = meta_list.nested.first()
+        {
+            if path.is_ident("unit") && meta_list.nested.len() == 1 {
+                return if DURATION_UNITS.contains(&value.value().as_str()) {
+                    Ok((Some(attr), value.value()))
+                } else {
+                    Err(syn::Error::new_spanned(
+                        value,
+                        "unknown duration unit, expected one of \"ns\", \"us\", \"ms\", \"s\", \"m\", \"h\" or \"d\"",
+                    ))
+                };
+            }
+        }
+    }
+
+    Err(syn::Error::new_spanned(
+        attr,
+        "expected #[duration(unit = \"...\")]",
+    ))
+}
+
+/// The attribute options that control how a field's value is parsed.
+struct ParseOptions {
+    list: ListOptions,
+    duration_unit: String,
+}
+
+/// Builds an expression that parses the `&str` produced by `value` into a `Result<T, String>`,
+/// where `T` is the type `ty` described by `field_type`.
+fn get_parse_expression(
+    field_type: &PrimitiveType,
+    ty: &syn::Type,
+    options: &ParseOptions,
+    value: TokenStream2,
+) -> TokenStream2 {
+    match field_type {
+        PrimitiveType::String => quote! { Ok::<String, String>(#value.to_string()) },
+        PrimitiveType::Number => {
+            quote! { #value.trim().parse::<#ty>().map_err(|e| e.to_string()) }
+        }
+        PrimitiveType::Bool => quote! { ::env_extract::parse::boolean(#value) },
+        PrimitiveType::FromStr => {
+            quote! { #value.parse::<#ty>().map_err(|e| e.to_string()) }
+        }
+        PrimitiveType::Bytes => quote! {
+            ::env_extract::parse::bytes(#value).and_then(|bytes| {
+                <#ty as ::std::convert::TryFrom<u128>>::try_from(bytes)
+                    .map_err(|_| format!("{} bytes does not fit in {}", bytes, stringify!(#ty)))
+            })
+        },
+        PrimitiveType::Duration => {
+            let unit = &options.duration_unit;
+            quote! { ::env_extract::parse::duration(#value, #unit) }
+        }
+        PrimitiveType::Nested => quote! {},
+        PrimitiveType::Optional(inner) => {
+            let inner_ty = get_generic_argument(ty, "Option").unwrap();
+            let parse_inner = get_parse_expression(inner, inner_ty, options, value);
+            quote! { (#parse_inner).map(Some) }
+        }
+        PrimitiveType::Secret(inner) => {
+            let inner_ty = get_generic_argument(ty, "Secret").unwrap();
+            let parse_inner = get_parse_expression(inner, inner_ty, options, value);
+            quote! { (#parse_inner).map(::env_extract::Secret::new) }
+        }
+        PrimitiveType::List(inner) => {
+            let item_ty = get_list_element(ty).unwrap();
+            let parse_item = get_parse_expression(inner, item_ty, options, quote! { item });
+            let separator = &options.list.separator;
+            let trim = options.list.trim;
+            let skip_empty = options.list.skip_empty;
+            quote! {
+                ::env_extract::parse::split_list(#value, #separator, #trim, #skip_empty)
+                    .map(|item| {
+                        (#parse_item).map_err(|e| format!("invalid item {:?}: {}", item, e))
+                    })
+                    .collect::<Result<#ty, String>>()
+            }
+        }
+        PrimitiveType::ImplementedEnum => {
+            quote! { ::env_extract::__private::parse_variant::<#ty>(#value) }
+        }
+    }
+}
+
+/// A check from a `#[validate(...)]` attribute, along with the name it was written with.
+struct Validator {
+    name: syn::Ident,
+    kind: ValidatorKind,
+}
+
+enum ValidatorKind {
+    Range {
+        min: Option<syn::Expr>,
+        max: Option<syn::Expr>,
+    },
+    Length {
+        min: Option<syn::Expr>,
+        max: Option<syn::Expr>,
+    },
+    Regex(syn::LitStr),
+    OneOf(Vec<syn::LitStr>),
+    NonEmpty,
+    With(syn::Path),
+}
+
+impl syn::parse::Parse for Validator {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name: syn::Ident = input.parse()?;
+        let kind = match name.to_string().as_str() {
+            "range" | "length" => {
+                let (min, max) = parse_bounds(input, &name)?;
+                if name == "range" {
+                    ValidatorKind::Range { min, max }
+                } else {
+                    ValidatorKind::Length { min, max }
+                }
+            }
+            "regex" if cfg!(not(feature = "regex")) => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "#[validate(regex = ...)] requires the `regex` feature of env-extract",
+                ));
+            }
+            "regex" => {
+                input.parse::<syn::Token![=]>()?;
+                let pattern: syn::LitStr = input.parse()?;
+                check_pattern(&pattern)?;
+                ValidatorKind::Regex(pattern)
+            }
+            "one_of" => {
+                let content;
+                syn::parenthesized!(content in input);
+                let values =
+                    syn::punctuated::Punctuated::<syn::LitStr, syn::Token![,]>::parse_terminated(
+                        &content,
+                    )?;
+                if values.is_empty() {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        "expected #[validate(one_of(\"...\", ...))]",
+                    ));
+                }
+                ValidatorKind::OneOf(values.into_iter().collect())
+            }
+            "non_empty" => ValidatorKind::NonEmpty,
+            "with" => {
+                input.parse::<syn::Token![=]>()?;
+                ValidatorKind::With(input.parse()?)
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "unknown validator, expected `range`, `length`, `regex`, `one_of`, `non_empty` or `with`",
+                ))
+            }
+        };
+        Ok(Validator { name, kind })
+    }
+}
+
+/// Parses the `(min = ..., max = ...)` arguments of `range` and `length`.
+fn parse_bounds(
+    input: syn::parse::ParseStream,
+    name: &syn::Ident,
+) -> syn::Result<(Option<syn::Expr>, Option<syn::Expr>)> {
+    let content;
+    syn::parenthesized!(content in input);
+
+    let mut min = None;
+    let mut max = None;
+    while !content.is_empty() {
+        let key: syn::Ident = content.parse()?;
+        content.parse::<syn::Token![=]>()?;
+        let value: syn::Expr = content.parse()?;
+        match key.to_string().as_str() {
+            "min" => min = Some(value),
+            "max" => max = Some(value),
+            _ => return Err(syn::Error::new(key.span(), "expected `min` or `max`")),
+        }
+        if !content.is_empty() {
+            content.parse::<syn::Token![,]>()?;
+        }
+    }
+
+    if min.is_none() && max.is_none() {
+        return Err(syn::Error::new(
+            name.span(),
+            format!("expected #[validate({}(min = ..., max = ...))]", name),
+        ));
+    }
+    Ok((min, max))
+}
+
+/// Rejects a `regex` pattern that would not compile, so the mistake is reported when building
+/// rather than each time the field is loaded.
+#[cfg(feature = "regex")]
+fn check_pattern(pattern: &syn::LitStr) -> syn::Result<()> {
+    match regex_syntax::Parser::new().parse(&pattern.value()) {
+        Ok(..) => Ok(()),
+        Err(e) => Err(syn::Error::new(pattern.span(), e)),
+    }
+}
+
+#[cfg(not(feature = "regex"))]
+fn check_pattern(_: &syn::LitStr) -> syn::Result<()> {
+    Ok(())
+}
+
+fn get_validators(attrs: &[Attribute]) -> syn::Result<Vec<Validator>> {
+    let mut validators = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("validate")) {
+        let parsed = attr.parse_args_with(
+            syn::punctuated::Punctuated::<Validator, syn::Token![,]>::parse_terminated,
+        )?;
+        validators.extend(parsed);
+    }
+    Ok(validators)
+}
+
+/// Builds an expression that runs `validators` against `value`, a reference to the type `ty`
+/// described by `field_type`, and evaluates to a `Result<(), String>`.
+///
+/// `Option` fields are only checked when they are `Some`, and `Secret` fields are checked through
+/// `expose_secret()`. On lists, `length` and `non_empty` count the items, `with` receives the whole
+/// list and the other checks are applied to each item.
+fn get_validation_expression(
+    field_type: &PrimitiveType,
+    ty: &syn::Type,
+    validators: &[&Validator],
+) -> syn::Result<TokenStream2> {
+    let mut checks = Vec::new();
+    match field_type {
+        PrimitiveType::Optional(inner) => {
+            let inner_ty = get_generic_argument(ty, "Option").unwrap();
+            let check_inner = get_validation_expression(inner, inner_ty, validators)?;
+            return Ok(quote! {
+                match value {
+                    Some(value) => #check_inner,
+                    None => Ok(()),
+                }
+            });
+        }
+        PrimitiveType::Secret(inner) => {
+            let inner_ty = get_generic_argument(ty, "Secret").unwrap();
+            let check_inner = get_validation_expression(inner, inner_ty, validators)?;
+            return Ok(quote! {{
+                let value = value.expose_secret();
+                #check_inner
+            }});
+        }
+        PrimitiveType::List(inner) => {
+            let mut item_validators = Vec::new();
+            for validator in validators {
+                match &validator.kind {
+                    ValidatorKind::Length { min, max } => {
+                        checks.push(length_check(quote! { value.len() }, min, max))
+                    }
+                    ValidatorKind::NonEmpty => {
+                        checks.push(quote! { ::env_extract::validate::non_empty(value.len()) })
+                    }
+                    ValidatorKind::With(path) => checks.push(with_check(path)),
+                    _ => item_validators.push(*validator),
+                }
+            }
+            if !item_validators.is_empty() {
+                let item_ty = get_list_element(ty).unwrap();
+                let check_item = get_validation_expression(inner, item_ty, &item_validators)?;
+                checks.push(quote! { value.iter().try_for_each(|value| #check_item) });
+            }
+        }
+        _ => {
+            for validator in validators {
+                let check = match (&validator.kind, field_type) {
+                    (
+                        ValidatorKind::Range { min, max },
+                        PrimitiveType::Number
+                        | PrimitiveType::Bytes
+                        | PrimitiveType::Duration
+                        | PrimitiveType::FromStr,
+                    ) => {
+                        let min = bound(min, quote! { #ty });
+                        let max = bound(max, quote! { #ty });
+                        quote! { ::env_extract::validate::range(value, #min, #max) }
+                    }
+                    (ValidatorKind::Length { min, max }, PrimitiveType::String) => {
+                        length_check(quote! { value.chars().count() }, min, max)
+                    }
+                    (ValidatorKind::NonEmpty, PrimitiveType::String) => {
+                        quote! { ::env_extract::validate::non_empty(value.len()) }
+                    }
+                    (ValidatorKind::Regex(pattern), PrimitiveType::String) => {
+                        quote! {{
+                            static COMPILED: ::env_extract::__private::CompiledRegex =
+                                ::env_extract::__private::CompiledRegex::new();
+                            ::env_extract::__private::regex(value, #pattern, &COMPILED)
+                        }}
+                    }
+                    (ValidatorKind::OneOf(allowed), PrimitiveType::String) => {
+                        quote! { ::env_extract::validate::one_of(value, &[#(#allowed),*]) }
+                    }
+                    (ValidatorKind::With(path), _) => with_check(path),
+                    (ValidatorKind::Range { .. }, _) => {
+                        return Err(misplaced_validator(
+                            validator,
+                            "numbers, byte sizes, durations and #[parse] fields",
+                        ))
+                    }
+                    _ => return Err(misplaced_validator(validator, "`String` fields and lists")),
+                };
+                checks.push(check);
+            }
+        }
+    }
+
+    Ok(match checks.as_slice() {
+        [check] => check.clone(),
+        _ => quote! {
+            (|| -> Result<(), String> {
+                #(#checks?;)*
+                Ok(())
+            })()
+        },
+    })
+}
+
+/// Builds the `Constraint` that describes `validator` in the field's metadata.
+fn get_constraint(validator: &Validator) -> TokenStream2 {
+    let described_bound = |value: &Option<syn::Expr>| match value {
+        Some(value) => {
+            let value = describe_tokens(value.to_token_stream());
+            quote! { Some(#value) }
+        }
+        None => quote! { None },
+    };
+    match &validator.kind {
+        ValidatorKind::Range { min, max } => {
+            let (min, max) = (described_bound(min), described_bound(max));
+            quote! { ::env_extract::Constraint::Range { min: #min, max: #max } }
+        }
+        ValidatorKind::Length { min, max } => {
+            let (min, max) = (described_bound(min), described_bound(max));
+            quote! { ::env_extract::Constraint::Length { min: #min, max: #max } }
+        }
+        ValidatorKind::NonEmpty => quote! { ::env_extract::Constraint::NonEmpty },
+        ValidatorKind::Regex(pattern) => {
+            quote! { ::env_extract::Constraint::Regex(#pattern) }
+        }
+        ValidatorKind::OneOf(allowed) => {
+            quote! { ::env_extract::Constraint::OneOf(&[#(#allowed),*]) }
+        }
+        ValidatorKind::With(path) => {
+            let path = describe_tokens(path.to_token_stream());
+            quote! { ::env_extract::Constraint::With(#path) }
+        }
+    }
+}
+
+/// What was last written by [`describe_tokens`], which decides the spacing before the next token.
+#[derive(Clone, Copy, PartialEq)]
+enum Written {
+    Nothing,
+    Operand,
+    Comma,
+    /// A `::`, `.` or unary operator, which is written without a space after it.
+    Glue,
+    /// A binary operator, or with `joint` the first character of one such as `<=`.
+    Operator {
+        joint: bool,
+    },
+}
+
+/// Writes tokens the way they are usually formatted, as `Duration::from_secs(1)` rather than the
+/// `Duration :: from_secs(1)` that `stringify!` produces, for bounds shown in generated docs.
+fn describe_tokens(tokens: TokenStream2) -> String {
+    let mut out = String::new();
+    let mut written = Written::Nothing;
+    for token in tokens {
+        let spaced = matches!(written, Written::Comma | Written::Operator { joint: false });
+        match token {
+            TokenTree::Ident(..) | TokenTree::Literal(..) => {
+                if spaced || written == Written::Operand {
+                    out.push(' ');
+                }
+                out.push_str(&token.to_string());
+                written = Written::Operand;
+            }
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{ ", " }"),
+                    Delimiter::None => ("", ""),
+                };
+                if spaced {
+                    out.push(' ');
+                }
+                out.push_str(open);
+                out.push_str(&describe_tokens(group.stream()));
+                out.push_str(close);
+                written = Written::Operand;
+            }
+            TokenTree::Punct(punct) => {
+                let c = punct.as_char();
+                written = match (c, written) {
+                    (':' | '.', _) => {
+                        out.push(c);
+                        Written::Glue
+                    }
+                    (',', _) => {
+                        out.push(c);
+                        Written::Comma
+                    }
+                    (_, Written::Operator { joint: true }) => {
+                        out.push(c);
+                        Written::Operator {
+                            joint: punct.spacing() == Spacing::Joint,
+                        }
+                    }
+                    (_, Written::Operand) => {
+                        out.push(' ');
+                        out.push(c);
+                        Written::Operator {
+                            joint: punct.spacing() == Spacing::Joint,
+                        }
+                    }
+                    _ => {
+                        if spaced {
+                            out.push(' ');
+                        }
+                        out.push(c);
+                        Written::Glue
+                    }
+                };
+            }
+        }
+    }
+    out
+}
+
+fn bound(value: &Option<syn::Expr>, ty: TokenStream2) -> TokenStream2 {
+    match value {
+        Some(value) => quote! { Some::<#ty>(#value) },
+        None => quote! { None },
+    }
+}
+
+fn length_check(
+    len: TokenStream2,
+    min: &Option<syn::Expr>,
+    max: &Option<syn::Expr>,
+) -> TokenStream2 {
+    let min = bound(min, quote! { usize });
+    let max = bound(max, quote! { usize });
+    quote! { ::env_extract::validate::length(#len, #min, #max) }
+}
+
+fn with_check(path: &syn::Path) -> TokenStream2 {
+    quote! { #path(value).map_err(|e| e.to_string()) }
+}
+
+fn misplaced_validator(validator: &Validator, allowed: &str) -> syn::Error {
+    syn::Error::new(
+        validator.name.span(),
+        format!(
+            "#[validate({})] can only be used on {}",
+            validator.name, allowed
+        ),
+    )
+}
+
+/// Reads the function named by `#[config(validate = "...")]` on a struct.
+fn get_struct_validator(attrs: &[Attribute]) -> syn::Result<Option<syn::Path>> {
+    let mut validator = None;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("config")) {
+        let meta_list = match attr.parse_meta()? {
+            Meta::List(meta_list) => meta_list,
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    "expected #[config(validate = \"...\")]",
+                ))
+            }
+        };
+
+        for nested_meta in meta_list.nested {
+            match nested_meta {
+                syn::NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(value),
+                    ..
+                })) if path.is_ident("validate") => validator = Some(value.parse()?),
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        "unknown config option, expected `validate = \"...\"`",
+                    ))
+                }
+            }
+        }
+    }
+    Ok(validator)
+}
+
+/// A `#[required_if(...)]` or `#[conflicts_with(...)]` rule on a field, checked once the fields
+/// have been loaded, whether or not all of them loaded successfully.
+enum FieldRule {
+    RequiredIf {
+        other: syn::Ident,
+        value: Option<Lit>,
+    },
+    ConflictsWith(syn::Ident),
+}
+
+impl FieldRule {
+    fn other(&self) -> &syn::Ident {
+        match self {
+            FieldRule::RequiredIf { other, .. } | FieldRule::ConflictsWith(other) => other,
+        }
+    }
+}
+
+fn get_field_rules(attrs: &[Attribute]) -> syn::Result<Vec<FieldRule>> {
+    let mut rules = Vec::new();
+    for attr in attrs {
+        if attr.path.is_ident("required_if") {
+            let error = "expected #[required_if(field)] or #[required_if(field = value)]";
+            let nested_meta = match attr.parse_meta()? {
+                Meta::List(meta_list) if meta_list.nested.len() == 1 => {
+                    meta_list.nested.into_iter().next().unwrap()
+                }
+                other => return Err(syn::Error::new_spanned(other, error)),
+            };
+            let (path, value) = match nested_meta {
+                syn::NestedMeta::Meta(Meta::Path(path)) => (path, None),
+                syn::NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
+                    (path, Some(lit))
+                }
+                other => return Err(syn::Error::new_spanned(other, error)),
+            };
+            match path.get_ident() {
+                Some(other) => rules.push(FieldRule::RequiredIf {
+                    other: other.clone(),
+                    value,
+                }),
+                None => return Err(syn::Error::new_spanned(path, error)),
+            }
+        } else if attr.path.is_ident("conflicts_with") {
+            let error = "expected #[conflicts_with(field, ...)]";
+            let meta_list = match attr.parse_meta()? {
+                Meta::List(meta_list) if !meta_list.nested.is_empty() => meta_list,
+                other => return Err(syn::Error::new_spanned(other, error)),
+            };
+            for nested_meta in meta_list.nested {
+                match nested_meta {
+                    syn::NestedMeta::Meta(Meta::Path(path)) if path.get_ident().is_some() => {
+                        rules.push(FieldRule::ConflictsWith(path.get_ident().unwrap().clone()))
+                    }
+                    other => return Err(syn::Error::new_spanned(other, error)),
+                }
+            }
+        }
+    }
+    Ok(rules)
+}
+
+/// Builds an expression that evaluates to whether the loaded `field` equals `value`, as given in
+/// `#[required_if(field = value)]`. `Option` fields are compared when they are `Some`, and
+/// `#[enumerated]` fields are compared by the variant that `value` selects.
+fn get_required_value_check(field: &syn::Field, value: &Lit) -> syn::Result<TokenStream2> {
+    let other = field.ident.as_ref().unwrap();
+    let field_type = get_function_primitive_type(&field.ty, &field.attrs)?;
+    let (value_type, loaded) = match &field_type {
+        PrimitiveType::Optional(inner) => (
+            &**inner,
+            quote! { #other.as_ref().and_then(Option::as_ref) },
+        ),
+        other_type => (other_type, quote! { #other.as_ref() }),
+    };
+
+    let expected = match value_type {
+        PrimitiveType::String => "a string",
+        PrimitiveType::Bool => "`true` or `false`",
+        PrimitiveType::Number | PrimitiveType::Bytes => "a number",
+        PrimitiveType::ImplementedEnum => "a string that selects one of the enum's variants",
+        _ => {
+            return Err(syn::Error::new_spanned(
+                value,
+                format!(
+                    "#[required_if({} = ...)] can only compare `String`, `bool`, number and #[enumerated] fields, or `Option`s of them",
+                    other
+                ),
+            ))
+        }
+    };
+    let compare = match (value_type, value) {
+        (PrimitiveType::String, Lit::Str(..))
+        | (PrimitiveType::Bool, Lit::Bool(..))
+        | (PrimitiveType::Number | PrimitiveType::Bytes, Lit::Int(..) | Lit::Float(..)) => {
+            quote! { ::env_extract::__private::equals(other, &#value) }
+        }
+        (PrimitiveType::ImplementedEnum, Lit::Str(..)) => {
+            quote! { ::env_extract::__private::is_variant(other, #value) }
+        }
+        _ => {
+            return Err(syn::Error::new_spanned(
+                value,
+                format!("expected {} to compare with `{}`", expected, other),
+            ))
+        }
+    };
+    Ok(quote! { #loaded.is_some_and(|other| #compare) })
+}
+
+/// Joins the `///` documentation of an item, one line per `///` line.
+fn get_doc(attrs: &[Attribute]) -> String {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(line),
+                ..
+            })) => Some(line.value()),
+            _ => None,
+        })
+        .map(|line| match line.strip_prefix(' ') {
+            Some(line) => line.to_string(),
+            None => line,
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Builds the `FieldKind` describing the type `ty`, which `field_type` classifies.
+fn get_field_kind(
+    field_type: &PrimitiveType,
+    ty: &syn::Type,
+    options: &ParseOptions,
+) -> TokenStream2 {
+    match field_type {
+        PrimitiveType::String => quote! { ::env_extract::FieldKind::String },
+        PrimitiveType::Number => match ty.to_token_stream().to_string().as_str() {
+            "f32" | "f64" => quote! { ::env_extract::FieldKind::Float },
+            _ => quote! { ::env_extract::FieldKind::Integer },
+        },
+        PrimitiveType::Bool => quote! { ::env_extract::FieldKind::Bool },
+        PrimitiveType::Bytes => quote! { ::env_extract::FieldKind::Bytes },
+        PrimitiveType::Duration => {
+            let unit = &options.duration_unit;
+            quote! { ::env_extract::FieldKind::Duration { unit: #unit } }
+        }
+        PrimitiveType::FromStr => quote! { ::env_extract::FieldKind::FromStr },
+        PrimitiveType::Optional(inner) => {
+            get_field_kind(inner, get_generic_argument(ty, "Option").unwrap(), options)
+        }
+        PrimitiveType::Secret(inner) => {
+            get_field_kind(inner, get_generic_argument(ty, "Secret").unwrap(), options)
+        }
+        PrimitiveType::List(inner) => {
+            let item = get_field_kind(inner, get_list_element(ty).unwrap(), options);
+            let separator = &options.list.separator;
+            quote! {
+                ::env_extract::FieldKind::List {
+                    item: &#item,
+                    separator: #separator,
+                }
+            }
+        }
+        PrimitiveType::ImplementedEnum => quote! {
+            ::env_extract::FieldKind::Enum {
+                variants: <#ty as ::env_extract::EnvVar>::VARIANTS,
+            }
+        },
+        PrimitiveType::Nested => quote! {
+            ::env_extract::FieldKind::Nested {
+                fields: <#ty as ::env_extract::ConfigStruct>::FIELDS,
+            }
+        },
+    }
+}
+
+fn get_generic_argument<'a>(ty: &'a syn::Type, wrapper: &str) -> Option<&'a syn::Type> {
+    let segment = match ty {
+        syn::Type::Path(type_path) => type_path.path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident != wrapper {
+        return None;
+    }
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => {
+            match arguments.args.first() {
+                Some(syn::GenericArgument::Type(inner)) => Some(inner),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Whether `ty` is written as one of `paths`, such as `std::time::Duration`, ignoring a leading
+/// `::` and the generic arguments of the last segment.
+fn is_path_to(ty: &syn::TypePath, paths: &[&str]) -> bool {
+    if ty.qself.is_some() {
+        return false;
+    }
+    let path: Vec<String> = ty
+        .path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    paths.contains(&path.join("::").as_str())
+}
+
+fn get_function_primitive_type(
+    ty: &syn::Type,
+    attributes: &[Attribute],
+) -> syn::Result<PrimitiveType> {
+    if attributes.iter().any(|attr| attr.path.is_ident("nested")) {
+        return match ty {
+            syn::Type::Path(..) => Ok(PrimitiveType::Nested),
+            _ => Err(unsupported_type(ty)),
+        };
+    }
+
+    if let Some(inner) = get_generic_argument(ty, "Option") {
+        return match get_function_primitive_type(inner, attributes)? {
+            PrimitiveType::Optional(..) => Err(unsupported_type(ty)),
+            inner_type => Ok(PrimitiveType::Optional(Box::new(inner_type))),
+        };
+    }
+
+    // With #[parse], a type named `Secret` is loaded through its own `FromStr`, since it may come
+    // from another crate such as `secrecy`.
+    if let (false, Some(inner)) = (
+        get_empty_path_attribute(attributes, "parse"),
+        get_secret_argument(ty),
+    ) {
+        let inner_type = get_function_primitive_type(inner, attributes)?;
+        if !is_zeroizable(&inner_type, inner) {
+            return Err(syn::Error::new_spanned(
+                inner,
+                "`Secret` can only hold `String`, `bool`, a number or a `Vec` of these, which can be wiped from memory when they are dropped",
+            ));
+        }
+        return Ok(PrimitiveType::Secret(Box::new(inner_type)));
+    }
+
+    if let Some(item) = get_list_element(ty) {
+        return match get_function_primitive_type(item, attributes)? {
+            item_type @ (PrimitiveType::String
+            | PrimitiveType::Number
+            | PrimitiveType::Bool
+            | PrimitiveType::Bytes
+            | PrimitiveType::Duration
+            | PrimitiveType::FromStr
+            | PrimitiveType::ImplementedEnum) => Ok(PrimitiveType::List(Box::new(item_type))),
+            _ => Err(unsupported_type(item)),
+        };
+    }
+
+    match ty {
+        syn::Type::Path(type_path) => {
+            let bytes = get_empty_path_attribute(attributes, "bytes");
+            let type_name = match type_path.clone().into_token_stream().to_string().as_str() {
+                "String" => Some(PrimitiveType::String),
+                "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64"
+                | "i128" | "isize"
+                    if bytes =>
+                {
+                    Some(PrimitiveType::Bytes)
+                }
+                "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64"
+                | "i128" | "isize" | "f32" | "f64" => Some(PrimitiveType::Number),
+                "bool" => Some(PrimitiveType::Bool),
+                _ => None,
+            };
+
+            if let Some(t) = type_name {
+                return Ok(t);
+            }
+
+            if get_empty_path_attribute(attributes, "parse") {
+                return Ok(PrimitiveType::FromStr);
+            }
+
+            if is_path_to(
+                type_path,
+                &["Duration", "std::time::Duration", "core::time::Duration"],
+            ) {
+                return Ok(PrimitiveType::Duration);
+            }
+
+            if let Some(segment) = type_path.path.segments.last() {
+                if segment.arguments.is_empty()
+                    && get_empty_path_attribute(attributes, "enumerated")
+                {
+                    return Ok(PrimitiveType::ImplementedEnum);
+                }
+            }
+            Err(unsupported_type(ty))
+        }
+        _ => Err(unsupported_type(ty)),
+    }
+}
+
+fn unsupported_type(ty: &syn::Type) -> syn::Error {
+    syn::Error::new_spanned(
+        ty,
+        "unsupported field type, expected `String`, `bool`, a number, an enum deriving `EnvVar` marked with #[enumerated], `Duration`, a type implementing `FromStr` marked with #[parse], a `Vec` or set of these, an `Option` of one of these, or a `Secret` of a `String`, `bool`, number or `Vec` of them",
+    )
+}
+
+/// This proc macro derives the `ConfigStruct` trait for a struct, enabling easy extraction of
+/// fields from environment variables and parsing them to the correct type.
+///
+/// Fields wrapped in `Option<T>` are set to `None` when their environment variable is not set. A
+/// value that is set but cannot be parsed is still reported as an error. `bool` fields accept
+/// `true`/`false`, `yes`/`no`, `on`/`off` and `1`/`0` in any case, and are `false` when neither the
+/// variable nor a default is set.
+///
+/// The macro supports the following attributes on the struct itself:
+///
+/// - `env_prefix = "APP_"`: Places a prefix in front of every variable name derived from a field
+///   name, so `port` is read from `APP_PORT`, and of the enum variables of `#[enumerated]` fields.
+///   The prefix can be replaced at runtime by calling `get_with_prefix` or `try_get_with_prefix`.
+/// - `from_file`: Enables `from_file` for every field that supports it.
+/// - `config(validate = "check_config")`: Calls `check_config(&config) -> Result<(), E: Display>`
+///   after every field has been loaded, for rules that involve several fields.
+///
+/// The macro supports the following attributes for struct fields:
+///
+/// - `default`: Sets a default value for the field. If not provided, `get()` will panic and
+///   `try_get()` will return an error if the environment variable is not set.
+/// - `env_var`: Sets the name of the environment variable to use for the field. If not provided,
+///   the macro will use the name of the field in uppercase as the environment variable name.
+/// - `var_name(primary = "DB_URL", aliases = ["DATABASE_URL"], deprecated)`: Falls back to the
+///   aliases in order when the primary name is not set. `primary` defaults to the derived name.
+///   With `deprecated`, reading an alias is reported through `env_extract::set_deprecation_hook`.
+/// - `enumerated`: Identifies an enum that implements the `EnvVar` trait. The macro will parse the
+///   environment variable to the enum type. On a `Vec` or set of enums, the items are read from the
+///   field's own variable, and an item that selects no variant is an error. A `default` on the
+///   field is parsed as a variant name when the variable is not set.
+/// - `parse`: Parses the field with its `FromStr` implementation. This supports types such as
+///   `PathBuf`, `IpAddr` or your own newtypes, and reports the `FromStr::Err` message on failure.
+/// - `nested(prefix = "DB", separator = "_")`: Loads a field whose type also derives
+///   `ConfigStruct` by calling its `try_get_with_prefix`. The prefix defaults to the field name in
+///   uppercase and the separator to `_`.
+/// - `list(separator = ",", trim = true, skip_empty = true)`: Controls how `Vec`, `HashSet` and
+///   `BTreeSet` fields are split into items. The values shown are the defaults.
+/// - `from_file`: Reads the value from the file named by `<VAR>_FILE` when `VAR` is not set,
+///   without the trailing newline. Setting both is an error. Not supported on `#[nested]` or
+///   `#[enumerated]` fields.
+/// - `bytes`: Reads an integer field as a byte size with an SI or IEC suffix, such as `2GB` or
+///   `512MiB`. Sizes that overflow the field's type are reported as errors.
+/// - `duration(unit = "ms")`: Sets the unit of bare numbers in `Duration` fields, which otherwise
+///   are read as seconds. Values with units, such as `30s` or `1h30m`, are always accepted.
+/// - `secret`: Redacts the field's value in `describe()` reports and in parse errors, and replaces
+///   the messages of parse errors and failed `validate` checks, which may quote the value. Fields
+///   of type `env_extract::Secret<T>` are treated as secret without the attribute, and also keep
+///   their value out of `Debug` output and wipe it from memory when dropped. They can hold a
+///   `String`, `bool`, a number or a `Vec` of these. With `#[parse]`, a type named `Secret` is
+///   parsed with its own `FromStr`, so wrappers from other crates such as `secrecy` can be used,
+///   and is treated as secret too.
+/// - `validate(range(min = 1, max = 65535), length(max = 64), non_empty, regex = "^[a-z]+$",
+///   one_of("a", "b"), with = path::to::check)`: Checks the parsed value with the functions in
+///   `env_extract::validate`, or with a function taking a reference to the value and returning a
+///   `Result<(), E: Display>`. A failed check is reported as an invalid value for the field. The
+///   bounds of `range` are expressions of the field's type, such as `Duration::from_secs(1)` for a
+///   `Duration`, and `regex` patterns are checked when the macro expands.
+/// - `required_if(other)` or `required_if(other = value)`: Requires the field's variable to be set
+///   when the variable of the `other` field is set, or when `other` equals `value`. `other` can be a
+///   `String`, `bool`, number or `#[enumerated]` field, or an `Option` of one, which only matches
+///   when it is `Some`. Enum fields are compared by the variant that the string `value` selects.
+/// - `conflicts_with(other, ...)`: Rejects the field's variable being set together with the
+///   variable of any of the named fields.
+///
+/// The struct gets a `get()` constructor, which panics if any field cannot be loaded, and a
+/// `try_get()` constructor, which checks every field and returns an `env_extract::ConfigError`
+/// listing all of the problems it found. `get_from` and `try_get_from` read the variables from any
+/// `env_extract::Source` instead of the process environment. `describe()` reports where each
+/// field's value came from, and `env_example()` renders a `.env` template documenting every
+/// variable from the fields' `///` comments. `json_schema()` describes the same variables as a JSON
+/// Schema, and `markdown_table()` lists them in a Markdown table. All of these are built from the
+/// generated `FIELDS` constant, which describes every field.
+///
+/// Example usage:
+///
+/// ```rust
+/// use env_extract::ConfigStruct;
+/// use env_extract::EnvVar;
+/// #[derive(Debug, EnvVar)]
+/// #[var_name = "DATABASE_TYPE"]
+/// #[panic_on_invalid]
+/// #[case(convert = "lowercase")]
+/// enum DatabaseType {
+///     Postgres,
+///     Mysql,
+///     Sqlite,
+/// }
+///
+/// #[derive(ConfigStruct, Debug)]
+/// struct Config {
+///     db_host: String,
+///     db_port: u16,
+///     use_tls: bool,
+///
+///     #[enumerated]
+///     db_type: DatabaseType,
+/// }
+///
+/// fn main() {
+///     std::env::set_var("DB_HOST", "localhost");
+///     std::env::set_var("DB_PORT", "5432");
+///     std::env::set_var("USE_TLS", "true");
+///     std::env::set_var("DATABASE_TYPE", "postgres");
+///
+///     let config = Config::get();
+///
+///     assert_eq!(config.db_host, "localhost");
+///     assert_eq!(config.db_port, 5432);
+///     assert_eq!(config.use_tls, true);
+///     assert!(matches!(config.db_type, DatabaseType::Postgres));
+/// }
+/// ```
+///
+/// In the example above, the `ConfigStruct` trait is derived for the `Config` struct, allowing
+/// easy extraction of fields from environment variables. The `db_host`, `db_port`, and `use_tls`
+/// fields are extracted as `String`, `u16`, and `bool` types, respectively. The `db_type` field is
+/// extracted as an enum type `DatabaseType`, which is parsed from the environment variable named
+/// `DATABASE_TYPE` and converted to lowercase.
+#[proc_macro_derive(
+    ConfigStruct,
+    attributes(
+        default,
+        enumerated,
+        var_name,
+        list,
+        parse,
+        nested,
+        env_prefix,
+        secret,
+        from_file,
+        duration,
+        bytes,
+        validate,
+        config,
+        required_if,
+        conflicts_with
+    )
+)]
+pub fn env_for_struct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_env_for_struct(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_env_for_struct(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let struct_name = &input.ident;
+    let fields = match input.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(ref fields),
+            ..
+        }) => &fields.named,
+        syn::Data::Struct(ref s) => {
+            return Err(syn::Error::new_spanned(
+                &s.fields,
+                "ConfigStruct can only be derived for structs with named fields",
+            ))
+        }
+        _ => {
+            return Err(syn::Error::new_spanned(
+                struct_name,
+                "ConfigStruct can only be derived for structs",
+            ))
+        }
+    };
+
+    let env_prefix = get_env_prefix(&input.attrs)?;
+    let struct_from_file = get_empty_path_attribute(&input.attrs, "from_file");
+    let struct_validator = get_struct_validator(&input.attrs)?;
+
+    // Fields named by a `required_if` or `conflicts_with` rule record whether their variable was
+    // set, so the rules can be checked once the fields have been loaded.
+    let mut field_rules = Vec::new();
+    let mut tracked_fields = Vec::new();
+    for field in fields {
+        let field_ident = field.ident.as_ref().unwrap();
+        let rules = get_field_rules(&field.attrs)?;
+        for rule in &rules {
+            let other = rule.other();
+            if !fields
+                .iter()
+                .any(|field| field.ident.as_ref() == Some(other))
+            {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    format!("no field named `{}` in this struct", other),
+                ));
+            }
+            if let FieldRule::RequiredIf { value: None, .. } | FieldRule::ConflictsWith(..) = rule {
+                tracked_fields.push(other.clone());
+            }
+            tracked_fields.push(field_ident.clone());
+        }
+        if !rules.is_empty() {
+            field_rules.push((field_ident, rules));
+        }
+    }
+
+    let mut load_fields = Vec::new();
+    let mut field_idents = Vec::new();
+    let mut var_names = Vec::new();
+    let mut field_infos = Vec::new();
+    for field in fields {
+        let field_type = get_function_primitive_type(&field.ty, &field.attrs)?;
+        let field_ident = field.ident.as_ref().unwrap();
+        let field_ty = &field.ty;
+
+        let var_name = get_var_name(&field.attrs)?;
+        let (aliases, deprecated) = match &var_name {
+            Some(v) => (v.aliases.clone(), v.deprecated),
+            None => (Vec::new(), false),
+        };
+        let primary = var_name.and_then(|v| v.primary);
+        let derived_var_name = field_ident.to_token_stream().to_string().to_uppercase();
+        let var_name_to_check_for = match &primary {
+            Some(v) => quote! { #v.to_string() },
+            None => quote! { format!("{}{}", __prefix, #derived_var_name) },
+        };
+
+        let (value_type, value_ty, optional) = match &field_type {
+            PrimitiveType::Optional(inner) => (
+                &**inner,
+                get_generic_argument(field_ty, "Option").unwrap(),
+                true,
+            ),
+            other => (other, field_ty, false),
+        };
+
+        let default = get_default_value(&field.attrs)?;
+        let default_value = match &default {
+            Some(v) => quote! { Some(#v.to_string()) },
+            None => quote! { None },
+        };
+
+        let missing_value = match &field_type {
+            _ if optional => quote! { Ok(None) },
+            PrimitiveType::Bool => quote! { Ok(false) },
+            PrimitiveType::Secret(inner) if matches!(**inner, PrimitiveType::Bool) => {
+                quote! { Ok(::env_extract::Secret::new(false)) }
+            }
+            _ => quote! {
+                Err(::env_extract::FieldError::Missing {
+                    field: stringify!(#field_ident).to_string(),
+                    var_name: var_name.clone(),
+                })
+            },
+        };
+
+        let list_options = get_list_options(&field.attrs)?;
+        if let (Some(attr), false) = (&list_options.attribute, is_list_type(value_type)) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "#[list] can only be used on `Vec`, `HashSet` or `BTreeSet` fields",
+            ));
+        }
+
+        let bytes_attribute = field.attrs.iter().find(|attr| attr.path.is_ident("bytes"));
+        if let (Some(attr), false) = (bytes_attribute, is_bytes_type(value_type)) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "#[bytes] can only be used on integer fields or lists of them",
+            ));
+        }
+
+        let (duration_attribute, duration_unit) = get_duration_unit(&field.attrs)?;
+        if let (Some(attr), false) = (duration_attribute, is_duration_type(value_type)) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "#[duration] can only be used on `Duration` fields or lists of them",
+            ));
+        }
+
+        let parse_options = ParseOptions {
+            list: list_options,
+            duration_unit,
+        };
+        let parse_value =
+            get_parse_expression(&field_type, field_ty, &parse_options, quote! { v.as_str() });
+
+        // A #[parse] field of a `Secret` type from another crate is redacted like our own.
+        let secret = get_empty_path_attribute(&field.attrs, "secret")
+            || is_secret_type(value_type)
+            || matches!(value_type, PrimitiveType::FromStr)
+                && get_generic_argument(value_ty, "Secret").is_some();
+
+        let from_file_attribute = field
+            .attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("from_file"));
+        if let (Some(attr), PrimitiveType::Nested | PrimitiveType::ImplementedEnum) =
+            (from_file_attribute, value_type)
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "#[from_file] cannot be used on #[nested] or #[enumerated] fields",
+            ));
+        }
+        let from_file = struct_from_file || from_file_attribute.is_some();
+
+        let validators = get_validators(&field.attrs)?;
+        let validate_result = match (validators.first(), value_type) {
+            (None, _) => quote! {},
+            (Some(validator), PrimitiveType::Nested | PrimitiveType::ImplementedEnum) => {
+                return Err(syn::Error::new(
+                    validator.name.span(),
+                    "#[validate] cannot be used on #[nested] or #[enumerated] fields",
+                ));
+            }
+            (Some(..), _) => {
+                let validators: Vec<&Validator> = validators.iter().collect();
+                let check = get_validation_expression(&field_type, field_ty, &validators)?;
+                // Validator messages can reveal a secret's length or quote it, like parse errors.
+                let (invalid_value, message) = if secret {
+                    (
+                        quote! { #REDACTED.to_string() },
+                        quote! { ::env_extract::__private::redact_check_message(message) },
+                    )
+                } else {
+                    (
+                        quote! { raw.clone().unwrap_or_default() },
+                        quote! { message },
+                    )
+                };
+                quote! {
+                    let result = result.and_then(|value| {
+                        let check: Result<(), String> = {
+                            let value = &value;
+                            #check
+                        };
+                        match check {
+                            Ok(()) => Ok(value),
+                            Err(message) => Err(::env_extract::FieldError::Invalid {
+                                field: stringify!(#field_ident).to_string(),
+                                var_name: var_name.clone(),
+                                value: #invalid_value,
+                                message: #message,
+                            }),
+                        }
+                    });
+                }
+            }
+        };
+        let lookup = if aliases.is_empty() {
+            quote! {
+                let found = ::env_extract::__private::lookup(__source, &var_name, #from_file);
+            }
+        } else {
+            quote! {
+                let (var_name, found) = ::env_extract::__private::lookup_aliased(
+                    __source,
+                    var_name,
+                    &[#(#aliases),*],
+                    #deprecated,
+                    #from_file,
+                );
+            }
+        };
+        if let (false, PrimitiveType::Nested | PrimitiveType::ImplementedEnum) =
+            (aliases.is_empty(), value_type)
+        {
+            return Err(syn::Error::new_spanned(
+                field_ident,
+                "aliases cannot be used on #[nested] or #[enumerated] fields, set them on the enum's #[var_name] instead",
+            ));
+        }
+
+        let tracked = tracked_fields.contains(field_ident);
+        let set_ident = format_ident!("__{}_set", field_ident);
+        if let (true, PrimitiveType::Nested) = (tracked, value_type) {
+            return Err(syn::Error::new_spanned(
+                field_ident,
+                "#[required_if] and #[conflicts_with] cannot be used with #[nested] fields",
+            ));
+        }
+        if tracked {
+            load_fields.push(quote! { let #set_ident: bool; });
+        }
+        let (record_set, record_enum_set) = if tracked {
+            (
+                quote! {
+                    #set_ident = matches!(
+                        origin,
+                        ::env_extract::Origin::Variable | ::env_extract::Origin::File
+                    );
+                },
+                quote! { #set_ident = raw.is_some(); },
+            )
+        } else {
+            (quote! {}, quote! {})
+        };
+
+        let (invalid_value, invalid_message, redact_raw, report_value) = if secret {
+            (
+                quote! { #REDACTED.to_string() },
+                quote! { ::env_extract::__private::redact_message(message) },
+                quote! { let raw = ::env_extract::__private::redact(raw); },
+                quote! { #REDACTED.to_string() },
+            )
+        } else {
+            (
+                quote! { v.clone() },
+                quote! { message },
+                quote! {},
+                quote! {{
+                    use ::env_extract::__private::{DescribeDebug as _, DescribeOther as _};
+                    (&::env_extract::__private::Describe(v)).describe_value()
+                }},
+            )
+        };
+
+        let record_result = quote! {
+            #redact_raw
+            if let Some(__reports) = __reports.as_deref_mut() {
+                __reports.push(::env_extract::FieldReport {
+                    field: stringify!(#field_ident).to_string(),
+                    var_name,
+                    origin,
+                    raw,
+                    result: match &result {
+                        Ok(v) => Ok(#report_value),
+                        Err(e) => Err(e.clone()),
+                    },
+                    secret: #secret,
+                });
+            }
+
+            match result {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    __errors.push(e);
+                    None
+                }
+            }
+        };
+
+        let enum_value = if optional {
+            quote! { Ok(Some(v)) }
+        } else {
+            quote! { Ok(v) }
+        };
+        let enum_fallback = if optional {
+            quote! { Some(<#value_ty as ::env_extract::EnvVar>::fallback()) }
+        } else {
+            quote! { <#value_ty as ::env_extract::EnvVar>::fallback() }
+        };
+        let enum_fallback_value = quote! {
+            Err(..) if ::env_extract::__private::has_fallback(
+                <#value_ty as ::env_extract::EnvVar>::VARIANTS,
+            ) => Ok(#enum_fallback)
+        };
+        let enum_missing_value = if let Some(default) = &default {
+            quote! {
+                Err(::env_extract::EnvVarError::Missing { .. }) => {
+                    match ::env_extract::__private::parse_variant::<#value_ty>(#default) {
+                        Ok(v) => #enum_value,
+                        Err(message) => Err(::env_extract::FieldError::Invalid {
+                            field: stringify!(#field_ident).to_string(),
+                            var_name: var_name.clone(),
+                            value: #default.to_string(),
+                            message,
+                        }),
+                    }
+                }
+            }
+        } else if optional {
+            quote! { Err(::env_extract::EnvVarError::Missing { .. }) => Ok(None) }
+        } else {
+            quote! {
+                Err(::env_extract::EnvVarError::Missing { var_name }) => {
+                    Err(::env_extract::FieldError::Missing {
+                        field: stringify!(#field_ident).to_string(),
+                        var_name,
+                    })
+                }
+            }
+        };
+        // A missing variable selects the `#[default]` or leaves an `Option` unset, while any other
+        // enum that has a fallback variant resolves to it, as `EnvVar::get` does.
+        let enum_error_arms = if optional || default.is_some() {
+            [enum_missing_value, enum_fallback_value]
+        } else {
+            [enum_fallback_value, enum_missing_value]
+        };
+
+        let (info_var_name, prefixed, info_aliases) = match value_type {
+            PrimitiveType::Nested => {
+                let options = get_nested_options(&field.attrs, field_ident)?.unwrap();
+                let prefix = if options.prefix.is_empty() {
+                    String::new()
+                } else {
+                    options.prefix + &options.separator
+                };
+                (quote! { #prefix }, true, quote! { &[] })
+            }
+            PrimitiveType::ImplementedEnum => (
+                quote! { <#value_ty as ::env_extract::EnvVar>::VAR_NAME },
+                true,
+                quote! { <#value_ty as ::env_extract::EnvVar>::VAR_ALIASES },
+            ),
+            _ => (
+                match &primary {
+                    Some(v) => quote! { #v },
+                    None => quote! { #derived_var_name },
+                },
+                primary.is_none(),
+                quote! { &[#(#aliases),*] },
+            ),
+        };
+        let kind = get_field_kind(&field_type, field_ty, &parse_options);
+        let info_default = match &default {
+            Some(v) => quote! { Some(#v) },
+            None => quote! { None },
+        };
+        let doc = get_doc(&field.attrs);
+        let constraints = validators.iter().map(get_constraint);
+        let info_from_file = from_file
+            && !matches!(
+                value_type,
+                PrimitiveType::Nested | PrimitiveType::ImplementedEnum
+            );
+        field_infos.push(quote! {
+            ::env_extract::FieldInfo {
+                name: stringify!(#field_ident),
+                var_name: #info_var_name,
+                prefixed: #prefixed,
+                aliases: #info_aliases,
+                ty: stringify!(#field_ty),
+                kind: #kind,
+                optional: #optional,
+                default: #info_default,
+                doc: #doc,
+                secret: #secret,
+                from_file: #info_from_file,
+                constraints: &[#(#constraints),*],
+            }
+        });
+
+        var_names.push((
+            field_ident,
+            match value_type {
+                PrimitiveType::Nested => None,
+                PrimitiveType::ImplementedEnum => Some(quote! {
+                    format!("{}{}", __prefix, <#value_ty as ::env_extract::EnvVar>::VAR_NAME)
+                }),
+                _ => Some(var_name_to_check_for.clone()),
+            },
+        ));
+
+        load_fields.push(match value_type {
+            PrimitiveType::Nested => {
+                let options = get_nested_options(&field.attrs, field_ident)?.unwrap();
+                let nested_prefix = if options.prefix.is_empty() {
+                    quote! { __prefix.to_string() }
+                } else {
+                    let prefix = options.prefix + &options.separator;
+                    quote! { format!("{}{}", __prefix, #prefix) }
+                };
+
+                quote! {
+                    let #field_ident = {
+                        let start = __reports.as_deref().map_or(0, Vec::len);
+                        let result = <#value_ty as ::env_extract::ConfigStruct>::load_with_reports(
+                            __source,
+                            &#nested_prefix,
+                            __reports.as_deref_mut(),
+                        );
+
+                        if let Some(__reports) = __reports.as_deref_mut() {
+                            let nested: Vec<::env_extract::FieldReport> = __reports
+                                .drain(start..)
+                                .map(|report| report.nested_in(stringify!(#field_ident)))
+                                .collect();
+                            __reports.extend(nested);
+                        }
+
+                        match result {
+                            Ok(v) => Some(v),
+                            Err(e) => {
+                                __errors.extend(
+                                    e.into_errors()
+                                        .into_iter()
+                                        .map(|error| error.nested_in(stringify!(#field_ident))),
+                                );
+                                None
+                            }
+                        }
+                    };
+                }
+            }
+            PrimitiveType::ImplementedEnum => quote! {
+                let #field_ident = {
+                    let (var_name, raw) = ::env_extract::__private::lookup_enum(
+                        __source,
+                        __prefix,
+                        <#value_ty as ::env_extract::EnvVar>::VAR_NAME,
+                        <#value_ty as ::env_extract::EnvVar>::VAR_ALIASES,
+                    );
+                    #record_enum_set
+                    let (origin, raw) = match (raw, #default_value) {
+                        (Some(raw), _) => (::env_extract::Origin::Variable, Some(raw)),
+                        (None, Some(default)) => (::env_extract::Origin::Default, Some(default)),
+                        (None, None) => (::env_extract::Origin::Unset, None),
+                    };
+
+                    let result: Result<#field_ty, ::env_extract::FieldError> =
+                        match ::env_extract::__private::get_enum::<#value_ty>(__source, __prefix) {
+                            Ok(v) => #enum_value,
+                            #(#enum_error_arms,)*
+                            Err(source) => Err(::env_extract::FieldError::Enum {
+                                field: stringify!(#field_ident).to_string(),
+                                source,
+                            }),
+                        };
+
+                    #record_result
+                };
+            },
+            _ => quote! {
+                let #field_ident = {
+                    let var_name: String = #var_name_to_check_for;
+                    #lookup
+                    let (origin, raw, lookup_error) =
+                        match found {
+                            Ok(Some((v, origin))) => (origin, Some(v), None),
+                            Ok(None) => match #default_value {
+                                Some(v) => (::env_extract::Origin::Default, Some(v), None),
+                                None => (::env_extract::Origin::Unset, None, None),
+                            },
+                            Err(message) => (::env_extract::Origin::Unset, None, Some(message)),
+                        };
+                    #record_set
+
+                    let result: Result<#field_ty, ::env_extract::FieldError> = match (&raw, lookup_error) {
+                        (_, Some(message)) => Err(::env_extract::FieldError::File {
+                            field: stringify!(#field_ident).to_string(),
+                            var_name: var_name.clone(),
+                            message,
+                        }),
+                        (Some(v), None) => match #parse_value {
+                            Ok(parsed) => Ok(parsed),
+                            Err(message) => Err(::env_extract::FieldError::Invalid {
+                                field: stringify!(#field_ident).to_string(),
+                                var_name: var_name.clone(),
+                                value: #invalid_value,
+                                message: #invalid_message,
+                            }),
+                        },
+                        (None, None) => #missing_value,
+                    };
+                    #validate_result
+
+                    #record_result
+                };
+            },
+        });
+        field_idents.push(field_ident);
+    }
+
+    let var_name_of = |field_ident: &syn::Ident| {
+        var_names
+            .iter()
+            .find(|(ident, _)| *ident == field_ident)
+            .and_then(|(_, var_name)| var_name.clone())
+            .ok_or_else(|| {
+                syn::Error::new_spanned(
+                    field_ident,
+                    "#[required_if] and #[conflicts_with] cannot be used with #[nested] fields",
+                )
+            })
+    };
+
+    // Field rules only need the fields involved, so they are checked even when other fields
+    // failed to load, while the struct validator needs the whole struct.
+    let mut check_field_rules = Vec::new();
+    for (field_ident, rules) in &field_rules {
+        let set_ident = format_ident!("__{}_set", field_ident);
+        let var_name = var_name_of(field_ident)?;
+        for rule in rules {
+            let other = rule.other();
+            let other_set = format_ident!("__{}_set", other);
+            let other_var_name = var_name_of(other)?;
+            let (failed, message) = match rule {
+                FieldRule::RequiredIf { value: None, .. } => (
+                    quote! { #other_set && !#set_ident },
+                    quote! { format!("{} must be set when {} is set", #var_name, #other_var_name) },
+                ),
+                FieldRule::RequiredIf {
+                    value: Some(value), ..
+                } => (
+                    {
+                        let other_field = fields
+                            .iter()
+                            .find(|field| field.ident.as_ref() == Some(other))
+                            .unwrap();
+                        let equals = get_required_value_check(other_field, value)?;
+                        quote! { #equals && !#set_ident }
+                    },
+                    quote! {
+                        format!(
+                            "{} must be set when {} is {}",
+                            #var_name,
+                            #other_var_name,
+                            stringify!(#value),
+                        )
+                    },
+                ),
+                FieldRule::ConflictsWith(..) => (
+                    quote! { #set_ident && #other_set },
+                    quote! {
+                        format!(
+                            "{} cannot be set together with {}",
+                            #var_name,
+                            #other_var_name,
+                        )
+                    },
+                ),
+            };
+            check_field_rules.push(quote! {
+                if #failed {
+                    __errors.push(::env_extract::FieldError::Constraint {
+                        field: stringify!(#field_ident).to_string(),
+                        message: #message,
+                    });
+                }
+            });
+        }
+    }
+    let build_struct = match &struct_validator {
+        None if check_field_rules.is_empty() => quote! {
+            Ok(Self {
+                #(#field_idents,)*
+            })
+        },
+        None => quote! {
+            if __errors.is_empty() {
+                Ok(Self {
+                    #(#field_idents,)*
+                })
+            } else {
+                Err(::env_extract::ConfigError::new(__errors))
+            }
+        },
+        Some(validator) => quote! {{
+            let __value = Self {
+                #(#field_idents,)*
+            };
+            if let Err(e) = #validator(&__value) {
+                __errors.push(::env_extract::FieldError::Constraint {
+                    field: String::new(),
+                    message: e.to_string(),
+                });
+            }
+            if __errors.is_empty() {
+                Ok(__value)
+            } else {
+                Err(::env_extract::ConfigError::new(__errors))
+            }
+        }},
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics ::env_extract::ConfigStruct for #struct_name #ty_generics #where_clause {
+            const ENV_PREFIX: &'static str = #env_prefix;
+
+            const FIELDS: &'static [::env_extract::FieldInfo] = &[#(#field_infos),*];
+
+            fn load_with_reports(
+                __source: &dyn ::env_extract::Source,
+                __prefix: &str,
+                mut __reports: Option<&mut Vec<::env_extract::FieldReport>>,
+            ) -> Result<Self, ::env_extract::ConfigError> {
+                let mut __errors: Vec<::env_extract::FieldError> = Vec::new();
+
+                #(#load_fields)*
+
+                #(#check_field_rules)*
+
+                match (#(#field_idents,)*) {
+                    (#(Some(#field_idents),)*) => #build_struct,
+                    _ => Err(::env_extract::ConfigError::new(__errors)),
+                }
+            }
+        }
+    };
+
+    Ok(expanded)
+}
@@ -0,0 +1,73 @@
+//! Warnings for variables read under a deprecated name.
+
+use std::fmt;
+use std::sync::RwLock;
+
+/// A deprecated variable name that was read because the current name was not set, passed to the
+/// hook installed with [`set_deprecation_hook`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeprecatedVar {
+    /// The deprecated name that the value was read from.
+    pub name: String,
+    /// The name that should be used instead.
+    pub replacement: String,
+}
+
+impl fmt::Display for DeprecatedVar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is deprecated, set {} instead",
+            self.name, self.replacement
+        )
+    }
+}
+
+type Hook = Box<dyn Fn(&DeprecatedVar) + Send + Sync>;
+
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+
+/// Replaces the function that is called when a value is read from a deprecated alias, set with
+/// `#[var_name(aliases = [...], deprecated)]`.
+///
+/// Without a hook, the warning is logged with `log::warn!` when the `log` feature is enabled, and
+/// printed to standard error otherwise.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use std::sync::{Arc, Mutex};
+/// use env_extract::ConfigStruct;
+///
+/// #[derive(ConfigStruct)]
+/// struct Config {
+///     #[var_name(primary = "HOOK_EXAMPLE_DB_URL", aliases = ["HOOK_EXAMPLE_DATABASE_URL"], deprecated)]
+///     db_url: String,
+/// }
+///
+/// fn main() {
+///     let warnings = Arc::new(Mutex::new(Vec::new()));
+///     let recorded = warnings.clone();
+///     env_extract::set_deprecation_hook(move |var| recorded.lock().unwrap().push(var.to_string()));
+///
+///     let source = HashMap::from([("HOOK_EXAMPLE_DATABASE_URL", "postgres://localhost")]);
+///     let config = Config::get_from(&source);
+///     assert_eq!(config.db_url, "postgres://localhost");
+///     assert_eq!(
+///         *warnings.lock().unwrap(),
+///         ["HOOK_EXAMPLE_DATABASE_URL is deprecated, set HOOK_EXAMPLE_DB_URL instead"]
+///     );
+/// }
+/// ```
+pub fn set_deprecation_hook(hook: impl Fn(&DeprecatedVar) + Send + Sync + 'static) {
+    *HOOK.write().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(hook));
+}
+
+pub(crate) fn warn(var: &DeprecatedVar) {
+    match &*HOOK.read().unwrap_or_else(|e| e.into_inner()) {
+        Some(hook) => hook(var),
+        #[cfg(feature = "log")]
+        None => log::warn!("{}", var),
+        #[cfg(not(feature = "log"))]
+        None => eprintln!("warning: {}", var),
+    }
+}
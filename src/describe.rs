@@ -0,0 +1,120 @@
+use std::fmt;
+
+use crate::FieldError;
+
+/// Where the value of a field came from, as reported by `describe()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Origin {
+    /// The variable was set in the environment or the source being read.
+    Variable,
+    /// The variable was not set, and the value was read from the file named by `<VAR>_FILE`.
+    File,
+    /// The variable was not set, and the field's `#[default(...)]` value was used.
+    Default,
+    /// The variable was not set and the field has no default.
+    Unset,
+}
+
+/// How a single field of a `ConfigStruct` was loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldReport {
+    /// The name of the field, with `#[nested]` fields written as `parent.field`.
+    pub field: String,
+    /// The name of the variable that was consulted.
+    pub var_name: String,
+    /// Whether the value came from the variable, a `<VAR>_FILE` file or the field's default.
+    pub origin: Origin,
+    /// The string that was parsed, or `None` if there was none. This is `"<redacted>"` for
+    /// `#[secret]` fields.
+    pub raw: Option<String>,
+    /// The parsed value formatted with `Debug`, or the error the field produced. Types that do not
+    /// implement `Debug` are shown as `<no Debug implementation>`, and `#[secret]` fields are
+    /// shown as `<redacted>`.
+    pub result: Result<String, FieldError>,
+    /// Whether the field is marked with `#[secret]`.
+    pub secret: bool,
+}
+
+impl FieldReport {
+    /// Returns the report with `parent` prepended to its field name, like
+    /// [`FieldError::nested_in`].
+    pub fn nested_in(mut self, parent: &str) -> Self {
+        self.field = format!("{}.{}", parent, self.field);
+        self.result = self.result.map_err(|e| e.nested_in(parent));
+        self
+    }
+}
+
+impl fmt::Display for FieldReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match &self.result {
+            Ok(value) => value,
+            Err(error) => return write!(f, "{}: {}", self.field, error),
+        };
+
+        write!(f, "{} = {} ", self.field, value)?;
+        match (&self.origin, &self.raw) {
+            (Origin::Variable, Some(raw)) if self.secret => {
+                write!(f, "(from {} = {})", self.var_name, raw)
+            }
+            (Origin::Variable, Some(raw)) => write!(f, "(from {} = {:?})", self.var_name, raw),
+            (Origin::File, Some(raw)) if self.secret => {
+                write!(f, "(from {}_FILE = {})", self.var_name, raw)
+            }
+            (Origin::File, Some(raw)) => {
+                write!(f, "(from {}_FILE = {:?})", self.var_name, raw)
+            }
+            (Origin::Default, Some(raw)) if self.secret => {
+                write!(f, "(default {}, {} not set)", raw, self.var_name)
+            }
+            (Origin::Default, Some(raw)) => {
+                write!(f, "(default {:?}, {} not set)", raw, self.var_name)
+            }
+            _ => write!(f, "({} not set)", self.var_name),
+        }
+    }
+}
+
+/// A report of where every field of a `ConfigStruct` came from, returned by `describe()`.
+///
+/// Its `Display` implementation prints one line per field:
+///
+/// ```text
+/// host = "db.internal" (from DB_HOST = "db.internal")
+/// port = 5432 (default "5432", DB_PORT not set)
+/// password = <redacted> (from DB_PASSWORD = <redacted>)
+/// timeout: Invalid value "soon" for 'timeout' (DB_TIMEOUT): invalid digit found in string
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Description {
+    fields: Vec<FieldReport>,
+}
+
+impl Description {
+    /// Creates a description from a list of field reports.
+    pub fn new(fields: Vec<FieldReport>) -> Self {
+        Description { fields }
+    }
+
+    /// Returns the report for every field, in declaration order.
+    pub fn fields(&self) -> &[FieldReport] {
+        &self.fields
+    }
+
+    /// Returns the report for `field`, using `parent.field` for `#[nested]` fields.
+    pub fn field(&self, field: &str) -> Option<&FieldReport> {
+        self.fields.iter().find(|report| report.field == field)
+    }
+}
+
+impl fmt::Display for Description {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, report) in self.fields.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", report)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,362 @@
+//! A parser for `.env` files.
+//!
+//! Values are read into a [`Dotenv`] instead of being written into the process environment, so a
+//! struct deriving `ConfigStruct` can be loaded from a file without touching global state.
+//!
+//! The following syntax is supported:
+//!
+//! ```text
+//! # Comments start with `#`, on their own line or after an unquoted value
+//! HOST=localhost
+//! export PORT=8080                      # `export` prefixes are ignored
+//! GREETING="Hello,\n\"World\""          # double quotes support escapes
+//! PATTERN='^[a-z]+$'                    # single quotes are taken literally
+//! URL=http://${HOST}:$PORT/             # `${VAR}` and `$VAR` are interpolated
+//! LOG_DIR=${LOG_ROOT:-/var/log}/app     # `${VAR:-default}` falls back when VAR is unset or empty
+//! CERTIFICATE="-----BEGIN CERTIFICATE-----
+//! MIIB...
+//! -----END CERTIFICATE-----"            # quoted values can span several lines
+//! ```
+//!
+//! Interpolated variables are looked up in the process environment, then in the variables defined
+//! earlier in the same file, which is the precedence `from_dotenv` loads them with. Unknown
+//! variables expand to an empty string. The name in `$VAR` ends at the first character that is not
+//! a letter, digit or `_`, so `$HOST.example.com` expands `HOST`, while names that contain a `.`
+//! have to be written as `${VAR}`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// The variables read from a `.env` file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dotenv {
+    vars: HashMap<String, String>,
+}
+
+impl Dotenv {
+    /// Reads and parses the file at `path`.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, DotenvError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|source| DotenvError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&contents)
+    }
+
+    /// Parses the contents of a `.env` file.
+    ///
+    /// ```rust
+    /// use env_extract::dotenv::Dotenv;
+    ///
+    /// let dotenv = Dotenv::parse("export HOST=localhost\nURL=\"http://${HOST}/\"").unwrap();
+    /// assert_eq!(dotenv.get("URL"), Some("http://localhost/"));
+    /// ```
+    pub fn parse(contents: &str) -> Result<Self, DotenvError> {
+        Parser::new(contents).parse()
+    }
+
+    /// Returns the value of `key`, if the file defines it.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.vars.get(key).map(String::as_str)
+    }
+
+    /// Iterates over every variable defined in the file, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.vars.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+/// An error returned while reading a `.env` file or loading a struct from one.
+#[derive(Debug)]
+pub enum DotenvError {
+    /// The file could not be read.
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// The file contains invalid syntax on the given (1-based) line.
+    Syntax { line: usize, message: String },
+    /// The file was read, but the struct could not be loaded from it.
+    Config(crate::ConfigError),
+}
+
+impl fmt::Display for DotenvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DotenvError::Io { path, source } => {
+                write!(f, "Could not read {}: {}", path.display(), source)
+            }
+            DotenvError::Syntax { line, message } => {
+                write!(f, "Invalid .env syntax on line {}: {}", line, message)
+            }
+            DotenvError::Config(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for DotenvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DotenvError::Io { source, .. } => Some(source),
+            DotenvError::Syntax { .. } => None,
+            DotenvError::Config(error) => Some(error),
+        }
+    }
+}
+
+impl From<crate::ConfigError> for DotenvError {
+    fn from(error: crate::ConfigError) -> Self {
+        DotenvError::Config(error)
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    vars: HashMap<String, String>,
+}
+
+impl Parser {
+    fn new(contents: &str) -> Self {
+        Parser {
+            chars: contents.chars().collect(),
+            pos: 0,
+            line: 1,
+            vars: HashMap::new(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, DotenvError> {
+        Err(DotenvError::Syntax {
+            line: self.line,
+            message: message.into(),
+        })
+    }
+
+    fn skip_inline_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.next();
+        }
+    }
+
+    fn skip_line(&mut self) {
+        while let Some(c) = self.next() {
+            if c == '\n' {
+                break;
+            }
+        }
+    }
+
+    fn parse(mut self) -> Result<Dotenv, DotenvError> {
+        loop {
+            while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+                self.next();
+            }
+
+            match self.peek() {
+                None => break,
+                Some('#') => self.skip_line(),
+                Some(_) => {
+                    let (key, value) = self.parse_assignment()?;
+                    self.vars.insert(key, value);
+                }
+            }
+        }
+
+        Ok(Dotenv { vars: self.vars })
+    }
+
+    fn parse_key(&mut self) -> String {
+        self.parse_name(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+    }
+
+    /// Reads the name after a bare `$`, which unlike a key cannot contain a `.`.
+    fn parse_bare_name(&mut self) -> String {
+        self.parse_name(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    fn parse_name(&mut self, accepts: impl Fn(char) -> bool) -> String {
+        let mut name = String::new();
+        while let Some(c) = self.peek() {
+            if accepts(c) {
+                name.push(c);
+                self.next();
+            } else {
+                break;
+            }
+        }
+        name
+    }
+
+    fn parse_assignment(&mut self) -> Result<(String, String), DotenvError> {
+        let mut key = self.parse_key();
+        if key == "export" && matches!(self.peek(), Some(' ' | '\t')) {
+            self.skip_inline_whitespace();
+            key = self.parse_key();
+        }
+
+        if key.is_empty() {
+            return self.error("expected a variable name");
+        }
+
+        self.skip_inline_whitespace();
+        if self.next() != Some('=') {
+            return self.error(format!("expected '=' after {}", key));
+        }
+        self.skip_inline_whitespace();
+
+        let value = match self.peek() {
+            Some('"') => self.parse_double_quoted()?,
+            Some('\'') => self.parse_single_quoted()?,
+            _ => return Ok((key, self.parse_unquoted())),
+        };
+
+        self.skip_inline_whitespace();
+        match self.peek() {
+            None | Some('\n') | Some('\r') => {}
+            Some('#') => self.skip_line(),
+            Some(c) => return self.error(format!("unexpected {:?} after quoted value", c)),
+        }
+
+        Ok((key, value))
+    }
+
+    fn parse_single_quoted(&mut self) -> Result<String, DotenvError> {
+        let start_line = self.line;
+        self.next();
+
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some('\'') => return Ok(value),
+                Some(c) => value.push(c),
+                None => {
+                    return Err(DotenvError::Syntax {
+                        line: start_line,
+                        message: "unterminated single-quoted value".to_string(),
+                    })
+                }
+            }
+        }
+    }
+
+    fn parse_double_quoted(&mut self) -> Result<String, DotenvError> {
+        let start_line = self.line;
+        self.next();
+
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => match self.next() {
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some(c @ ('"' | '\\' | '$' | '\'')) => value.push(c),
+                    Some(c) => {
+                        value.push('\\');
+                        value.push(c);
+                    }
+                    None => break,
+                },
+                Some('$') => self.parse_interpolation(&mut value)?,
+                Some(c) => value.push(c),
+                None => break,
+            }
+        }
+
+        Err(DotenvError::Syntax {
+            line: start_line,
+            message: "unterminated double-quoted value".to_string(),
+        })
+    }
+
+    fn parse_unquoted(&mut self) -> String {
+        let mut value = String::new();
+        while let Some(c) = self.peek() {
+            match c {
+                '\n' => break,
+                '#' if value.is_empty() || value.ends_with([' ', '\t']) => {
+                    self.skip_line();
+                    break;
+                }
+                '$' => {
+                    self.next();
+                    // Interpolation in unquoted values cannot fail: an unterminated `${` is kept
+                    // as literal text.
+                    let (pos, line) = (self.pos, self.line);
+                    if self.parse_interpolation(&mut value).is_err() {
+                        self.pos = pos;
+                        self.line = line;
+                        value.push('$');
+                    }
+                }
+                _ => {
+                    value.push(c);
+                    self.next();
+                }
+            }
+        }
+        value.trim_end().to_string()
+    }
+
+    /// Expands `${NAME}`, `${NAME:-default}` or `$NAME` after a `$` has been consumed.
+    fn parse_interpolation(&mut self, value: &mut String) -> Result<(), DotenvError> {
+        if self.peek() == Some('{') {
+            self.next();
+
+            let mut expression = String::new();
+            loop {
+                match self.next() {
+                    Some('}') => break,
+                    Some('\n') | None => return self.error("unterminated ${...} expression"),
+                    Some(c) => expression.push(c),
+                }
+            }
+
+            let (name, default) = match expression.split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (expression.as_str(), None),
+            };
+            let found = self
+                .lookup(name)
+                .filter(|v| default.is_none() || !v.is_empty());
+            match (found, default) {
+                (Some(v), _) => value.push_str(&v),
+                (None, Some(default)) => value.push_str(default),
+                (None, None) => {}
+            }
+            return Ok(());
+        }
+
+        let name = self.parse_bare_name();
+        if name.is_empty() {
+            value.push('$');
+        } else if let Some(v) = self.lookup(&name) {
+            value.push_str(&v);
+        }
+        Ok(())
+    }
+
+    fn lookup(&self, name: &str) -> Option<String> {
+        std::env::var(name)
+            .ok()
+            .or_else(|| self.vars.get(name).cloned())
+    }
+}
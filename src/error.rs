@@ -0,0 +1,198 @@
+use std::fmt;
+
+/// The error returned by `get_result()` on an enum deriving `EnvVar`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvVarError {
+    /// The environment variable was not set.
+    Missing { var_name: String },
+    /// The environment variable was set, but its value did not match any of the enum's variants.
+    Invalid {
+        var_name: String,
+        value: String,
+        /// The values that would have been accepted, after case conversion.
+        expected: &'static [&'static str],
+    },
+}
+
+impl EnvVarError {
+    /// The name of the environment variable that was read.
+    pub fn var_name(&self) -> &str {
+        match self {
+            EnvVarError::Missing { var_name } | EnvVarError::Invalid { var_name, .. } => var_name,
+        }
+    }
+}
+
+impl fmt::Display for EnvVarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvVarError::Missing { var_name } => {
+                write!(f, "Environment variable '{}' is not set", var_name)
+            }
+            EnvVarError::Invalid {
+                var_name,
+                value,
+                expected,
+            } => write!(
+                f,
+                "Invalid environment variable value {:?} for '{}', expected one of: {}",
+                value,
+                var_name,
+                expected.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EnvVarError {}
+
+/// A single problem found while loading one field of a `ConfigStruct`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldError {
+    /// The environment variable was not set and the field has no default value.
+    Missing { field: String, var_name: String },
+    /// The environment variable was set, but its value could not be parsed into the field's type.
+    Invalid {
+        field: String,
+        var_name: String,
+        value: String,
+        message: String,
+    },
+    /// An `#[enumerated]` field did not match any variant of its enum.
+    Enum { field: String, source: EnvVarError },
+    /// A `#[from_file]` field could not be read from the file named by `<VAR>_FILE`, or both
+    /// `VAR` and `<VAR>_FILE` were set.
+    File {
+        field: String,
+        var_name: String,
+        message: String,
+    },
+    /// A rule involving several fields failed: a `#[required_if]` field was not set, a
+    /// `#[conflicts_with]` field was set alongside the other field, or the struct's
+    /// `#[config(validate = "...")]` function returned an error. `field` is empty for errors
+    /// returned by the validation function of the top-level struct.
+    Constraint { field: String, message: String },
+}
+
+impl FieldError {
+    /// The name of the struct field this error belongs to.
+    pub fn field(&self) -> &str {
+        match self {
+            FieldError::Missing { field, .. }
+            | FieldError::Invalid { field, .. }
+            | FieldError::Enum { field, .. }
+            | FieldError::File { field, .. }
+            | FieldError::Constraint { field, .. } => field,
+        }
+    }
+
+    /// Returns the error with `parent` prepended to its field name, so that an error in the `host`
+    /// field of a `#[nested]` field called `db` is reported for `db.host`.
+    pub fn nested_in(mut self, parent: &str) -> Self {
+        match &mut self {
+            FieldError::Missing { field, .. }
+            | FieldError::Invalid { field, .. }
+            | FieldError::Enum { field, .. }
+            | FieldError::File { field, .. } => *field = format!("{}.{}", parent, field),
+            FieldError::Constraint { field, .. } if field.is_empty() => *field = parent.to_string(),
+            FieldError::Constraint { field, .. } => *field = format!("{}.{}", parent, field),
+        }
+        self
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldError::Missing { field, var_name } => write!(
+                f,
+                "No environment variable or default value found for '{}' ({})",
+                field, var_name
+            ),
+            FieldError::Invalid {
+                field,
+                var_name,
+                value,
+                message,
+            } => write!(
+                f,
+                "Invalid value {:?} for '{}' ({}): {}",
+                value, field, var_name, message
+            ),
+            FieldError::Enum { field, source } => {
+                write!(f, "Invalid value for '{}': {}", field, source)
+            }
+            FieldError::File {
+                field,
+                var_name,
+                message,
+            } => write!(f, "Could not load '{}' ({}): {}", field, var_name, message),
+            FieldError::Constraint { field, message } if field.is_empty() => {
+                write!(f, "Invalid configuration: {}", message)
+            }
+            FieldError::Constraint { field, message } => {
+                write!(f, "Invalid configuration for '{}': {}", field, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FieldError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FieldError::Enum { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// The error returned by `try_get()` on a struct deriving `ConfigStruct`.
+///
+/// Every field is checked before this error is returned, so it lists all of the problems with the
+/// current environment rather than only the first one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    errors: Vec<FieldError>,
+}
+
+impl ConfigError {
+    /// Creates a new error from a list of field errors.
+    pub fn new(errors: Vec<FieldError>) -> Self {
+        ConfigError { errors }
+    }
+
+    /// Returns every field error that was found.
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+
+    /// Consumes the error and returns the list of field errors.
+    pub fn into_errors(self) -> Vec<FieldError> {
+        self.errors
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let [error] = self.errors.as_slice() {
+            return write!(f, "{}", error);
+        }
+
+        write!(f, "{} configuration errors:", self.errors.len())?;
+        for error in &self.errors {
+            write!(f, "\n  - {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl IntoIterator for ConfigError {
+    type Item = FieldError;
+    type IntoIter = std::vec::IntoIter<FieldError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
@@ -0,0 +1,85 @@
+//! `.env` templates rendered from the fields of a `ConfigStruct`.
+
+use crate::{FieldInfo, FieldKind};
+
+/// Renders a `.env` template for `fields`, read under `prefix`.
+pub(crate) fn render(fields: &[FieldInfo], prefix: &str) -> String {
+    let mut out = String::new();
+    write_fields(&mut out, fields, prefix);
+    out
+}
+
+fn write_fields(out: &mut String, fields: &[FieldInfo], prefix: &str) {
+    for field in fields {
+        if let FieldKind::Nested { fields } = field.kind {
+            write_fields(out, fields, &field.var_name_with_prefix(prefix));
+            continue;
+        }
+
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        for line in field.doc.lines() {
+            write_comment(out, line);
+        }
+
+        let var_name = field.var_name_with_prefix(prefix);
+        let allowed = match field.kind {
+            FieldKind::Enum { variants } => Some(("One of", variants)),
+            FieldKind::List {
+                item: FieldKind::Enum { variants },
+                ..
+            } => Some(("Each item one of", *variants)),
+            _ => None,
+        };
+        if let Some((label, variants)) = allowed {
+            let values: Vec<&str> = variants
+                .iter()
+                .filter(|variant| !variant.is_invalid_fallback())
+                .map(|variant| variant.value)
+                .collect();
+            write_comment(out, &format!("{}: {}", label, values.join(", ")));
+        }
+        let aliases = field.aliases_with_prefix(prefix);
+        if !aliases.is_empty() {
+            write_comment(out, &format!("Also read from: {}", aliases.join(", ")));
+        }
+        if field.from_file {
+            write_comment(
+                out,
+                &format!("Can be read from the file named by {}_FILE.", var_name),
+            );
+        }
+
+        match field.default {
+            _ if field.required() => out.push_str(&format!("{}=\n", var_name)),
+            Some(default) if !field.secret => {
+                out.push_str(&format!("# {}={}\n", var_name, quote(default)))
+            }
+            _ => out.push_str(&format!("# {}=\n", var_name)),
+        }
+    }
+}
+
+fn write_comment(out: &mut String, line: &str) {
+    if line.is_empty() {
+        out.push_str("#\n");
+    } else {
+        out.push_str(&format!("# {}\n", line));
+    }
+}
+
+/// Quotes values that the `.env` parser would otherwise read differently.
+fn quote(value: &str) -> String {
+    if value.is_empty() || value.contains(|c: char| c.is_whitespace() || "#\"'$\\".contains(c)) {
+        format!(
+            "\"{}\"",
+            value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('$', "\\$")
+        )
+    } else {
+        value.to_string()
+    }
+}
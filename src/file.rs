@@ -0,0 +1,239 @@
+//! Configuration files in TOML or JSON format.
+//!
+//! A [`ConfigFile`] flattens a file into variables with the same names a `ConfigStruct` reads from
+//! the environment, so it can be used as a [`Source`](crate::Source) or as a layer of
+//! [`Layered`](crate::Layered):
+//!
+//! - Keys are uppercased, and the keys of nested tables are joined with `_`, so `port` under a
+//!   `[db]` table becomes `DB_PORT`.
+//! - Strings are used as they are, and numbers and booleans are formatted as text.
+//! - Arrays of strings, numbers or booleans are joined with `,`, the default separator of list
+//!   fields.
+//! - JSON `null` values are treated as if the key was not present.
+//!
+//! TOML support requires the `toml` feature and JSON support requires the `json` feature. Both
+//! are enabled by default.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// The variables read from a TOML or JSON configuration file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigFile {
+    vars: BTreeMap<String, String>,
+}
+
+impl ConfigFile {
+    /// Reads and parses the TOML file at `path`.
+    #[cfg(feature = "toml")]
+    pub fn toml(path: impl AsRef<std::path::Path>) -> Result<Self, ConfigFileError> {
+        Self::parse_toml(&read(path.as_ref())?)
+    }
+
+    /// Parses the contents of a TOML file.
+    ///
+    /// ```rust
+    /// use env_extract::file::ConfigFile;
+    ///
+    /// let file = ConfigFile::parse_toml("[db]\nhost = \"localhost\"\nports = [5432, 5433]").unwrap();
+    /// assert_eq!(file.get("DB_HOST"), Some("localhost"));
+    /// assert_eq!(file.get("DB_PORTS"), Some("5432,5433"));
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn parse_toml(contents: &str) -> Result<Self, ConfigFileError> {
+        let table: toml::Table =
+            contents
+                .parse()
+                .map_err(|e: toml::de::Error| ConfigFileError::Syntax {
+                    message: e.message().to_string(),
+                })?;
+
+        let mut file = ConfigFile::default();
+        file.insert_toml_table("", &table)?;
+        Ok(file)
+    }
+
+    /// Reads and parses the JSON file at `path`.
+    #[cfg(feature = "json")]
+    pub fn json(path: impl AsRef<std::path::Path>) -> Result<Self, ConfigFileError> {
+        Self::parse_json(&read(path.as_ref())?)
+    }
+
+    /// Parses the contents of a JSON file, which must contain an object.
+    ///
+    /// ```rust
+    /// use env_extract::file::ConfigFile;
+    ///
+    /// let file = ConfigFile::parse_json(r#"{"db": {"host": "localhost", "tls": true}}"#).unwrap();
+    /// assert_eq!(file.get("DB_HOST"), Some("localhost"));
+    /// assert_eq!(file.get("DB_TLS"), Some("true"));
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn parse_json(contents: &str) -> Result<Self, ConfigFileError> {
+        let value: serde_json::Value =
+            serde_json::from_str(contents).map_err(|e| ConfigFileError::Syntax {
+                message: e.to_string(),
+            })?;
+        let object = match value {
+            serde_json::Value::Object(object) => object,
+            _ => {
+                return Err(ConfigFileError::Syntax {
+                    message: "expected a JSON object at the top level".to_string(),
+                })
+            }
+        };
+
+        let mut file = ConfigFile::default();
+        file.insert_json_object("", &object)?;
+        Ok(file)
+    }
+
+    /// Returns the value of `key`, if the file defines it.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.vars.get(key).map(String::as_str)
+    }
+
+    /// Iterates over every variable defined in the file, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.vars.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    #[cfg(feature = "toml")]
+    fn insert_toml_table(
+        &mut self,
+        prefix: &str,
+        table: &toml::Table,
+    ) -> Result<(), ConfigFileError> {
+        for (key, value) in table {
+            let name = join_key(prefix, key);
+            match value {
+                toml::Value::Table(table) => self.insert_toml_table(&name, table)?,
+                toml::Value::Array(items) => {
+                    let items = items
+                        .iter()
+                        .map(|item| toml_scalar(item).ok_or_else(|| unsupported_array(&name)))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    self.vars.insert(name, items.join(","));
+                }
+                scalar => {
+                    self.vars.insert(name, toml_scalar(scalar).unwrap());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "json")]
+    fn insert_json_object(
+        &mut self,
+        prefix: &str,
+        object: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), ConfigFileError> {
+        for (key, value) in object {
+            let name = join_key(prefix, key);
+            match value {
+                serde_json::Value::Null => {}
+                serde_json::Value::Object(object) => self.insert_json_object(&name, object)?,
+                serde_json::Value::Array(items) => {
+                    let items = items
+                        .iter()
+                        .map(|item| json_scalar(item).ok_or_else(|| unsupported_array(&name)))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    self.vars.insert(name, items.join(","));
+                }
+                scalar => {
+                    self.vars.insert(name, json_scalar(scalar).unwrap());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error returned while reading a TOML or JSON configuration file.
+#[derive(Debug)]
+pub enum ConfigFileError {
+    /// The file could not be read.
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// The file is not valid TOML or JSON.
+    Syntax { message: String },
+    /// The file contains a value that cannot be turned into a variable, such as an array of
+    /// tables.
+    Unsupported { key: String, message: String },
+}
+
+impl fmt::Display for ConfigFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigFileError::Io { path, source } => {
+                write!(f, "Could not read {}: {}", path.display(), source)
+            }
+            ConfigFileError::Syntax { message } => {
+                write!(f, "Invalid configuration file: {}", message)
+            }
+            ConfigFileError::Unsupported { key, message } => {
+                write!(f, "Unsupported value for {}: {}", key, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigFileError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(any(feature = "toml", feature = "json"))]
+fn read(path: &std::path::Path) -> Result<String, ConfigFileError> {
+    std::fs::read_to_string(path).map_err(|source| ConfigFileError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(any(feature = "toml", feature = "json"))]
+fn join_key(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_uppercase()
+    } else {
+        format!("{}_{}", prefix, key.to_uppercase())
+    }
+}
+
+#[cfg(any(feature = "toml", feature = "json"))]
+fn unsupported_array(key: &str) -> ConfigFileError {
+    ConfigFileError::Unsupported {
+        key: key.to_string(),
+        message: "arrays may only contain strings, numbers or booleans".to_string(),
+    }
+}
+
+#[cfg(feature = "toml")]
+fn toml_scalar(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(s) => Some(s.clone()),
+        toml::Value::Integer(i) => Some(i.to_string()),
+        toml::Value::Float(f) => Some(f.to_string()),
+        toml::Value::Boolean(b) => Some(b.to_string()),
+        toml::Value::Datetime(d) => Some(d.to_string()),
+        toml::Value::Array(_) | toml::Value::Table(_) => None,
+    }
+}
+
+#[cfg(feature = "json")]
+fn json_scalar(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,131 @@
+//! Configuration merged from several sources in priority order.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use crate::{ConfigError, ConfigStruct, Source};
+
+/// An ordered list of named [`Source`]s, where each layer overrides the ones added before it.
+///
+/// Every variable is read from the last layer that has it, and [`load`](Layered::load) records
+/// which layer that was:
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use env_extract::dotenv::Dotenv;
+/// use env_extract::file::ConfigFile;
+/// use env_extract::{ConfigStruct, Env, Layered};
+///
+/// #[derive(ConfigStruct)]
+/// struct Config {
+///     layered_example_host: String,
+///     layered_example_port: u16,
+///     layered_example_debug: bool,
+/// }
+///
+/// # #[cfg(feature = "toml")]
+/// fn main() {
+///     std::env::remove_var("LAYERED_EXAMPLE_HOST");
+///     std::env::remove_var("LAYERED_EXAMPLE_PORT");
+///     std::env::set_var("LAYERED_EXAMPLE_DEBUG", "true");
+///
+///     let file = ConfigFile::parse_toml("[layered_example]\nhost = \"example.com\"").unwrap();
+///     let dotenv = Dotenv::parse("LAYERED_EXAMPLE_PORT=8080").unwrap();
+///
+///     let loaded = Layered::new()
+///         .layer("defaults", HashMap::from([("LAYERED_EXAMPLE_HOST", "localhost")]))
+///         .layer("config.toml", file)
+///         .layer(".env", dotenv)
+///         .layer("env", Env)
+///         .load::<Config>()
+///         .unwrap();
+///
+///     assert_eq!(loaded.value.layered_example_host, "example.com");
+///     assert_eq!(loaded.origin("LAYERED_EXAMPLE_HOST"), Some("config.toml"));
+///     assert_eq!(loaded.origin("LAYERED_EXAMPLE_PORT"), Some(".env"));
+///     assert_eq!(loaded.origin("LAYERED_EXAMPLE_DEBUG"), Some("env"));
+/// }
+/// # #[cfg(not(feature = "toml"))]
+/// # fn main() {}
+/// ```
+#[derive(Default)]
+pub struct Layered<'a> {
+    layers: Vec<(String, Box<dyn Source + 'a>)>,
+}
+
+impl<'a> Layered<'a> {
+    /// Creates a configuration with no layers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a layer called `name`, which takes precedence over every layer added before it.
+    pub fn layer(mut self, name: impl Into<String>, source: impl Source + 'a) -> Self {
+        self.layers.push((name.into(), Box::new(source)));
+        self
+    }
+
+    /// Returns the value of `key` together with the name of the layer that supplied it.
+    pub fn lookup(&self, key: &str) -> Option<(String, &str)> {
+        self.layers
+            .iter()
+            .rev()
+            .find_map(|(name, source)| source.get(key).map(|value| (value, name.as_str())))
+    }
+
+    /// Returns the name of the layer that `key` is read from, if any layer has it.
+    pub fn origin(&self, key: &str) -> Option<&str> {
+        self.lookup(key).map(|(_, name)| name)
+    }
+
+    /// Loads `T` from the layers, recording which layer supplied each variable.
+    pub fn load<T: ConfigStruct>(&self) -> Result<Loaded<T>, ConfigError> {
+        let recorder = Recorder {
+            layered: self,
+            origins: RefCell::new(BTreeMap::new()),
+        };
+        let value = T::try_get_from(&recorder)?;
+        Ok(Loaded {
+            value,
+            origins: recorder.origins.into_inner(),
+        })
+    }
+}
+
+impl Source for Layered<'_> {
+    fn get(&self, key: &str) -> Option<String> {
+        self.lookup(key).map(|(value, _)| value)
+    }
+}
+
+/// A struct loaded by [`Layered::load`], along with the layer that supplied each variable.
+#[derive(Debug, Clone)]
+pub struct Loaded<T> {
+    /// The loaded struct.
+    pub value: T,
+    /// The name of the layer each variable was read from, keyed by variable name. Variables that
+    /// no layer had are not listed; their fields were left empty or set to their `#[default]`.
+    pub origins: BTreeMap<String, String>,
+}
+
+impl<T> Loaded<T> {
+    /// Returns the name of the layer that `var_name` was read from.
+    pub fn origin(&self, var_name: &str) -> Option<&str> {
+        self.origins.get(var_name).map(String::as_str)
+    }
+}
+
+struct Recorder<'l, 'a> {
+    layered: &'l Layered<'a>,
+    origins: RefCell<BTreeMap<String, String>>,
+}
+
+impl Source for Recorder<'_, '_> {
+    fn get(&self, key: &str) -> Option<String> {
+        let (value, name) = self.layered.lookup(key)?;
+        self.origins
+            .borrow_mut()
+            .insert(key.to_string(), name.to_string());
+        Some(value)
+    }
+}
@@ -28,27 +28,184 @@
 //! the environment variables by name. The following types are valid for fields of a struct:
 //!
 //! - `String`
-//! - `bool`
+//! - `bool`, which accepts `true`/`false`, `yes`/`no`, `on`/`off` and `1`/`0` in any case, and is
+//!   `false` when the variable is not set and the field has no default
 //! - `u8`, `u16`, `u32`, `u64`, `u128`
 //! - `i8`, `i16`, `i32`, `i64`, `i128`
 //! - `f32`, `f64`
+//! - `std::time::Duration`, written as `30s`, `500ms`, `1h30m` or `2d`
 //! - An enum that derives `EnvVar`
+//! - Any type that implements `FromStr`, when the field is marked with `#[parse]`
+//! - A struct that derives `ConfigStruct`, when the field is marked with `#[nested]`
+//! - `Vec<T>`, `HashSet<T>` or `BTreeSet<T>` of `String`, `bool`, a number, a `Duration`, a
+//!   `#[parse]` type or an `#[enumerated]` enum, read from a comma-separated list
+//! - [`Secret<T>`] of `String`, `bool`, a number or a `Vec` of these, which redacts the value in
+//!   `Debug` output and wipes it from memory when it is dropped. With `#[parse]`, a type named
+//!   `Secret`, such as `secrecy::Secret<String>`, is loaded with its own `FromStr` instead, and is
+//!   redacted as well
+//! - `Option<T>` of any of the above, which is `None` when the environment variable is not set
+//!
+//! The `ConfigStruct` macro supports the following attributes on the struct itself:
+//!
+//! - `env_prefix = "APP_"`: Places a prefix in front of every variable name derived from a field
+//!   name, so a `port` field is read from `APP_PORT`, and in front of the variables of
+//!   `enumerated` fields. Names set with `var_name` are not prefixed. The prefix can be replaced
+//!   at runtime with `get_with_prefix("OTHER_")`.
+//! - `from_file`: Enables `from_file` for every field that supports it, see below.
+//! - `config(validate = "check_config")`: Calls `check_config(&config)` once every field has been
+//!   loaded, for rules that involve several fields such as `MIN_POOL <= MAX_POOL`. The function
+//!   returns `Result<(), E>` with `E: Display`, and an error is reported as a
+//!   [`FieldError::Constraint`].
 //!
 //! The `ConfigStruct` macro supports the following attributes on the fields in the struct:
 //!
-//! - `default`: Sets a default value for the field. If this is not provided, the macro will panic
-//!   if the environment variable is not set.
+//! - `default`: Sets a default value for the field. If this is not provided, `get()` will panic
+//!   and `try_get()` will return an error if the environment variable is not set.
 //! - `env_var`: Sets the name of the environment variable to use for this field. If this is not
 //!   provided, the macro will use the name of the field in uppercase as the environment variable
 //!   name.
+//! - `var_name(primary = "DB_URL", aliases = ["DATABASE_URL"], deprecated)`: Reads the field from
+//!   `DB_URL`, falling back to each alias in order when it is not set. `primary` can be left out to
+//!   keep the name derived from the field. With `deprecated`, reading an alias calls the hook set
+//!   with [`set_deprecation_hook`], which by default logs a warning through the `log` crate when
+//!   the `log` feature is enabled and prints it to standard error otherwise.
 //! - `enumerated`: Identifies an enum that implements the `EnvVar` trait. The macro will parse the
-//!   environment variable to the enum type.
+//!   environment variable to the enum type. As with `EnvVar::get`, a missing or unrecognised value
+//!   selects the enum's `#[default]` or `Invalid` variant, and is only an error for enums marked
+//!   with `#[panic_on_invalid]`. A `#[default("Variant")]` on the field selects the variant it
+//!   names when the variable is not set.
+//! - `parse`: Parses the field with its `FromStr` implementation. This allows any type that
+//!   implements `FromStr`, such as `PathBuf`, `IpAddr` or your own newtypes, and includes the
+//!   `FromStr::Err` message in the error if parsing fails.
+//! - `nested(prefix = "DB", separator = "_")`: Loads a field whose type also derives
+//!   `ConfigStruct`, reading its variables under a prefix. By default the prefix is the field name
+//!   in uppercase followed by `_`, so `db.host` is read from `DB_HOST`.
+//! - `list(separator = ",", trim = true, skip_empty = true)`: Controls how list fields are split.
+//!   `separator` sets the delimiter, `trim` removes whitespace around each item and `skip_empty`
+//!   drops empty items instead of parsing them.
+//! - `from_file`: Lets the value be read from a file, for Docker and Kubernetes secrets. When
+//!   `DB_PASSWORD` is not set but `DB_PASSWORD_FILE=/run/secrets/db_password` is, the contents of
+//!   that file are used, without the trailing newline. Setting both variables is an error. This
+//!   cannot be used on `#[nested]` or `#[enumerated]` fields.
+//! - `bytes`: Reads an integer field as a byte size, accepting SI suffixes such as `k`, `MB` or
+//!   `GB` (powers of 1000) and IEC suffixes such as `KiB`, `MiB` or `GiB` (powers of 1024), so
+//!   `512MiB` is `536870912`. A size that does not fit in the field's type is an error.
+//! - `duration(unit = "ms")`: Sets the unit of `Duration` values written as a bare number, such as
+//!   `250`. Bare numbers are read as seconds by default. The supported units are `ns`, `us`, `ms`,
+//!   `s`, `m`, `h` and `d`.
+//! - `secret`: Redacts the field's value in `describe()` reports and in parse errors, whose message
+//!   is replaced since parsers often quote the value. The messages of failed `validate` checks are
+//!   replaced as well, since they can give away the value's length. `Secret<T>` fields are always
+//!   treated as secret, and their value can only be read with `expose_secret()`.
+//! - `validate(...)`: Checks the parsed value and reports a failed check as an invalid value. The
+//!   checks are `range(min = 1, max = 65535)` for numbers, byte sizes and durations,
+//!   `length(min = 1, max = 64)` and `non_empty` for strings and lists, `regex = "^[a-z]+$"`
+//!   (behind the default `regex` feature) and `one_of("debug", "info")` for strings, and
+//!   `with = path::to::check` for a function taking a reference to the value and returning
+//!   `Result<(), E>` with `E: Display`. Either bound of `range` and `length` may be left out.
+//!   The bounds of `range` are expressions of the field's type, so durations are checked with
+//!   `range(min = Duration::from_secs(1))`. A `regex` pattern that does not compile is reported
+//!   when building. `Option` fields are only checked when they are set, and on lists the checks
+//!   other than `length`, `non_empty` and `with` apply to each item. See the [`validate`] module.
+//! - `required_if(use_tls = true)`: Requires the field's variable to be set when the `use_tls`
+//!   field equals the given value, or with `required_if(proxy)` when the `proxy` field's variable
+//!   is set. This is usually placed on an `Option` field. `use_tls` can be a `String`, `bool`,
+//!   number or `#[enumerated]` field, or an `Option` of one, and an enum is compared by the variant
+//!   that a string such as `required_if(mode = "tls")` selects.
+//! - `conflicts_with(password)`: Reports an error when both this field's variable and the
+//!   `password` field's variable are set.
+//!
+//! The `required_if` and `conflicts_with` rules are checked after the fields have been loaded, even
+//! when other fields failed, while `config(validate)` needs every field to have loaded
+//! successfully. Broken rules are reported as [`FieldError::Constraint`] errors.
+//!
+//! The derived struct gets two constructors. `get()` panics on the first problem it reports,
+//! while `try_get()` checks every field and returns a [`ConfigError`] listing all missing
+//! variables, parse failures and invalid enum values at once:
+//!
+//! ```rust
+//! use env_extract::ConfigStruct;
+//!
+//! #[derive(ConfigStruct, Debug)]
+//! struct ServerConfig {
+//!     server_host: String,
+//!     server_port: u16,
+//! }
+//!
+//! fn main() {
+//!     std::env::remove_var("SERVER_HOST");
+//!     std::env::set_var("SERVER_PORT", "not a port");
+//!
+//!     let error = ServerConfig::try_get().unwrap_err();
+//!     assert_eq!(error.errors().len(), 2);
+//! }
+//! ```
+//!
+//! Every derived type can also be loaded from another [`Source`] with `get_from(&source)` and
+//! `try_get_from(&source)`. Sources are provided for the process environment ([`Env`]),
+//! `HashMap` and `BTreeMap` values, parsed `.env` files and TOML or JSON files, and two sources
+//! can be layered with [`Source::or`] so that the first one takes precedence. See the [`source`]
+//! module for details.
+//!
+//! Deployments that merge values from several places can stack sources with [`Layered`]. Each
+//! layer overrides the ones added before it, and [`Layered::load`] reports which layer supplied
+//! each variable. TOML and JSON files can be used as layers through [`file::ConfigFile`], which
+//! turns `port` under a `[db]` table into `DB_PORT`:
+//!
+//! ```rust
+//! # use env_extract::ConfigStruct;
+//! # #[derive(ConfigStruct)]
+//! # struct Config { layered_doc_port: u16 }
+//! use std::collections::HashMap;
+//! use env_extract::file::ConfigFile;
+//! use env_extract::{Env, Layered};
+//!
+//! # #[cfg(feature = "json")]
+//! # fn main() {
+//! let loaded = Layered::new()
+//!     .layer("defaults", HashMap::from([("LAYERED_DOC_PORT", "80")]))
+//!     .layer("config.json", ConfigFile::parse_json(r#"{"layered_doc": {"port": 8080}}"#).unwrap())
+//!     .layer("env", Env)
+//!     .load::<Config>()
+//!     .unwrap();
+//!
+//! assert_eq!(loaded.value.layered_doc_port, 8080);
+//! assert_eq!(loaded.origin("LAYERED_DOC_PORT"), Some("config.json"));
+//! # }
+//! # #[cfg(not(feature = "json"))]
+//! # fn main() {}
+//! ```
+//!
+//! To find out where a value came from, `describe()` returns a [`Description`] listing, for every
+//! field, the variable that was consulted, whether the variable or a `#[default(...)]` supplied
+//! the value, the raw string and the parsed value or error. Printing it gives one line per field.
+//!
+//! `env_example()` renders a `.env` template listing every variable the struct reads, including
+//! those of `#[nested]` and `#[enumerated]` fields, with the fields' `///` docs, defaults and the
+//! values each enum accepts. With the default `json` feature, `json_schema()` describes the same
+//! variables as a JSON Schema, including their types, defaults and `#[validate(...)]` checks, and
+//! enums deriving `EnvVar` have a `json_schema()` of their own. `markdown_table()` lists the
+//! variables in a Markdown table for the project's docs; comparing it with the checked-in table in
+//! a test keeps the docs from going stale.
+//!
+//! These are all built from `ConfigStruct::FIELDS`, which describes each field as a [`FieldInfo`]
+//! holding its name, variable, [`FieldKind`], default, docs and `#[validate(...)]` checks, and
+//! `EnvVar::VARIANTS`, which describes each variant of an enum as a [`VariantInfo`]. Both are
+//! public, so other tooling can be built from the same information.
+//!
+//! Structs can also be loaded from a `.env` file with `from_dotenv(path)`, or with
+//! `get_with_dotenv()` for the `.env` file in the current directory. The file is read by the
+//! built-in [`dotenv`] parser rather than being written into the process environment, and variables
+//! that are already set in the process environment take precedence over it, also when the file
+//! interpolates them with `${VAR}`.
 //!
 //! ## EnvVar Macro
 //!
 //! The `EnvVar` macro is applied to enums and implements the `EnvVar` trait, which provides a
 //! `.get()` method to retrieve a value of type `T` from an environment variable. The macro parses
-//! the environment variable to the enum type.
+//! the environment variable to the enum type. A `.get_result()` method is also provided, which
+//! returns an [`EnvVarError`] that tells a missing variable apart from an unrecognised value and
+//! lists the values that would have been accepted.
 //!
 //! The `EnvVar` macro requires one of the following conditions to be met for the enum:
 //!
@@ -63,6 +220,8 @@
 //!
 //! - `#[env_var = "FOO"]`: Set a custom environment variable name to search for. Defaults to the
 //!   name of the enum in uppercase.
+//! - `#[var_name(primary = "FOO", aliases = ["OLD_FOO"], deprecated)]`: Also reads the variable
+//!   from its aliases, as for struct fields.
 //! - `#[panic_on_invalid]`: Panics if a valid variant is not found.
 //! - `#[case(convert = "[uppercase|lowercase|exact|any]")]`: Converts all environment variable
 //!   values to a specific case before comparing them to map the valid variant. This attribute is
@@ -127,495 +286,416 @@
 //! struct is populated with values retrieved from environment variables, and assertions are used to
 //! validate the extracted values.
 
-use proc_macro::TokenStream;
-use quote::{quote, ToTokens};
-use syn::{parse_macro_input, Attribute, DeriveInput, Lit, Meta, MetaNameValue};
-
-/// This proc macro is applied to enums and implements the `EnvVar` trait, which provides a `.get()`
-/// method to retrieve a value of type `T` from an environment variable.
-///
-/// The macro parses the environment variable to the enum type and requires one of the following:
-/// - A variant called "Invalid", which will be returned if the environment variable does not match
-///   any of the variants.
-/// - A variant marked with `#[default]`, which will be returned if the environment variable does
-///   not match any of the variants.
-/// - The enum to be marked with `#[panic_on_invalid]`, which will panic if the environment variable
-///   does not match any of the variants.
-///
-/// The macro supports the following attributes on the enum itself:
-/// - `#[env_var = "FOO"]`: Set a custom environment variable name to search for. Defaults to the
-///   name of the enum in uppercase.
-/// - `#[panic_on_invalid]`: Panics if a valid variant is not found.
-/// - `#[case(convert = "[uppercase|lowercase|exact|any]")]`: Converts all environment variable
-///   values to a specific case before comparing them to map the valid variant. This attribute is
-///   overwritten if the variant also contains this attribute.
+pub use env_extract_derive::{ConfigStruct, EnvVar};
+
+#[doc(hidden)]
+#[path = "private.rs"]
+pub mod __private;
+mod deprecated;
+mod describe;
+pub mod dotenv;
+mod error;
+mod example;
+pub mod file;
+mod layered;
+mod markdown;
+mod meta;
+pub mod parse;
+#[cfg(feature = "json")]
+mod schema;
+mod secret;
+pub mod source;
+pub mod validate;
+
+pub use deprecated::{set_deprecation_hook, DeprecatedVar};
+pub use describe::{Description, FieldReport, Origin};
+pub use error::{ConfigError, EnvVarError, FieldError};
+pub use layered::{Layered, Loaded};
+pub use meta::{Constraint, FieldInfo, FieldKind, VariantInfo};
+pub use secret::Secret;
+pub use source::{Env, Source};
+
+/// A struct whose fields can be loaded from environment variables.
 ///
-/// The macro also supports the following attributes on the enum variants:
-/// - `#[case = "[uppercase|lowercase|exact|any]"]`: Specifies case conversion for the annotated
-///   enum variant. The `uppercase` and `lowercase` options convert the environment variable value
-///   to uppercase or lowercase before comparing it to the variant name. The `exact` option compares
-///   the environment variable value to the variant name without any case conversion. The `any`
-///   option converts both the environment variable value and the variant name to lowercase before
-///   comparing them.
-/// - `#[default]`: Specifies the default enum variant.
-/// - `#[ignore_variant]`: Ignores the annotated enum variant when checking for a match.
-///
-/// Example usage:
+/// This trait is implemented with `#[derive(ConfigStruct)]`. It can be used as a bound to write
+/// code that loads any configuration struct:
 ///
 /// ```rust
-/// use env_extract::EnvVar;
+/// use env_extract::{ConfigError, ConfigStruct};
 ///
-/// #[derive(EnvVar)]
-/// #[var_name = "DATABASE_TYPE"]
-/// #[case(convert = "uppercase")]
-/// enum DatabaseType {
-///     #[case(convert = "lowercase")]
-///     Postgres,
-///     Mysql,
+/// fn load<T: ConfigStruct>() -> Result<T, ConfigError> {
+///     T::try_get()
+/// }
 ///
-///     #[default]
-///     Sqlite,
+/// #[derive(ConfigStruct)]
+/// struct Config {
+///     load_example_host: String,
 /// }
 ///
 /// fn main() {
-///     std::env::set_var("DATABASE_TYPE", "MYSQL");
+///     std::env::set_var("LOAD_EXAMPLE_HOST", "localhost");
 ///
-///     let database_type = DatabaseType::get();
-///     assert!(matches!(database_type, DatabaseType::Mysql));
+///     let config: Config = load().unwrap();
+///     assert_eq!(config.load_example_host, "localhost");
 /// }
 /// ```
-///
-/// In the example above, the `EnvVar` trait is implemented for the `DatabaseType` enum, allowing
-/// the retrieval of a value from the "DATABASE_TYPE" environment variable. The enum variants are
-/// compared to the environment variable value after applying case conversions specified by the
-/// `#[case]` attributes. The `Mysql` variant is matched since the environment variable value is
-/// converted to uppercase and the variant name to lowercase, resulting in a match.
-#[proc_macro_derive(
-    EnvVar,
-    attributes(case, var_name, default, panic_on_invalid, ignore_variant)
-)]
-pub fn enum_from_env(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-
-    let enum_name = &input.ident;
-
-    let var_name_to_check_for = match get_var_name(&input.attrs) {
-        Some(v) => v,
-        None => enum_name.to_string().to_uppercase(),
-    };
-
-    let variants = match input.data {
-        syn::Data::Enum(ref variants) => &variants.variants,
-        _ => panic!("EnvVar can only be derived for enums"),
-    };
-
-    let mut invalid_type: Option<&syn::Ident> = None;
-
-    for variant in variants {
-        if &variant.ident.to_token_stream().to_string() == "Invalid" {
-            invalid_type = Some(&variant.ident);
-        };
+pub trait ConfigStruct: Sized {
+    /// The prefix set with `#[env_prefix = "..."]`, or an empty string if the struct has none.
+    const ENV_PREFIX: &'static str;
+
+    /// Loads every field of the struct, with `prefix` placed in front of each variable name derived
+    /// from a field name and of the variables of `#[enumerated]` fields. Names set with
+    /// `#[var_name]` are used as they are.
+    ///
+    /// The prefix replaces the struct's [`ENV_PREFIX`](ConfigStruct::ENV_PREFIX). This is how
+    /// `#[nested]` fields are loaded, and lets the same struct be read under different prefixes in
+    /// different places.
+    fn try_get_with_prefix(prefix: &str) -> Result<Self, ConfigError> {
+        Self::try_get_from_with_prefix(&Env, prefix)
     }
 
-    let mut default_value: Option<&syn::Ident> = None;
-
-    let panic_on_invalid = input.attrs.iter().any(|attr| {
-        if let Ok(Meta::Path(path)) = attr.parse_meta() {
-            path.is_ident("panic_on_invalid")
-        } else {
-            false
-        }
-    });
-
-    let default_case = get_case_conversion(&input.attrs);
-    let default_case_conversion = match default_case.0 {
-        CaseConversion::Uppercase => quote! { .to_uppercase() },
-        CaseConversion::Lowercase => quote! { .to_lowercase() },
-        CaseConversion::Exact => quote! {},
-        CaseConversion::Any => quote! { .to_lowercase() },
-    };
-
-    let mut check_variants = Vec::new();
-    let mut check_variants_result = Vec::new();
-    for variant in variants {
-        if let syn::Fields::Unit = variant.fields {
-            let ignore_variant = get_empty_path_attribute(&variant.attrs, "ignore_variant");
-
-            if ignore_variant {
-                continue;
-            }
-
-            let variant_name = &variant.ident;
-
-            let case = get_case_conversion(&variant.attrs);
-            if default_value.is_none() {
-                if get_empty_path_attribute(&variant.attrs, "default") {
-                    default_value = Some(variant_name);
-                }
-            }
-
-            let variant_case_conversion = if case.1 {
-                match case.0 {
-                    CaseConversion::Uppercase => quote! { .to_uppercase() },
-                    CaseConversion::Lowercase => quote! { .to_lowercase() },
-                    CaseConversion::Exact => quote! {},
-                    CaseConversion::Any => quote! { .to_lowercase() },
-                }
-            } else {
-                default_case_conversion.clone()
-            };
-
-            let var_case_conversion = if let CaseConversion::Any = case.0 {
-                quote! { .to_lowercase() }
-            } else {
-                quote! {}
-            };
-
-            check_variants.push(quote! {
-                if match std::env::var(#var_name_to_check_for) { Ok(v) => { Some((v)#var_case_conversion) }, Err(..) => None}.as_deref() == Some(&(stringify!(#variant_name)#variant_case_conversion)[..]) {
-                    return #enum_name::#variant_name;
-                }
-            });
-
-            check_variants_result.push(quote! {
-                if match std::env::var(#var_name_to_check_for) { Ok(v) => { Some((v)#var_case_conversion) }, Err(..) => None}.as_deref() == Some(&(stringify!(#variant_name)#variant_case_conversion)[..]) {
-                    return Ok(#enum_name::#variant_name);
-                }
-            });
-        }
+    /// Like [`try_get_with_prefix`](ConfigStruct::try_get_with_prefix), but reads the variables
+    /// from `source` instead of the process environment.
+    fn try_get_from_with_prefix(source: &dyn Source, prefix: &str) -> Result<Self, ConfigError> {
+        Self::load_with_reports(source, prefix, None)
     }
 
-    if invalid_type.is_none() && default_value.is_none() && !panic_on_invalid {
-        panic!("EnvVar Enum must have either an Invalid variant or specify a variant with the #[default] attribute");
+    #[doc(hidden)]
+    fn load_with_reports(
+        source: &dyn Source,
+        prefix: &str,
+        reports: Option<&mut Vec<FieldReport>>,
+    ) -> Result<Self, ConfigError>;
+
+    /// Describes the struct's fields in declaration order: the variable each one is read from, its
+    /// kind, default, documentation and checks. `#[nested]` fields list the fields of the nested
+    /// struct, and `#[enumerated]` fields the variants of the enum.
+    ///
+    /// This is what [`env_example`](ConfigStruct::env_example) and the other generated docs are
+    /// built from, and can be used to build other tooling on top of the struct:
+    ///
+    /// ```rust
+    /// use env_extract::{ConfigStruct, FieldKind};
+    ///
+    /// #[derive(ConfigStruct)]
+    /// #[env_prefix = "APP_"]
+    /// struct Config {
+    ///     /// Port to listen on.
+    ///     #[default(8080)]
+    ///     port: u16,
+    ///     #[var_name = "DATABASE_URL"]
+    ///     database_url: String,
+    /// }
+    ///
+    /// let port = &Config::FIELDS[0];
+    /// assert_eq!(port.name, "port");
+    /// assert_eq!(port.var_name_with_prefix(Config::ENV_PREFIX), "APP_PORT");
+    /// assert_eq!(port.kind, FieldKind::Integer);
+    /// assert_eq!(port.default, Some("8080"));
+    /// assert_eq!(port.doc, "Port to listen on.");
+    ///
+    /// let database_url = &Config::FIELDS[1];
+    /// assert_eq!(database_url.var_name_with_prefix(Config::ENV_PREFIX), "DATABASE_URL");
+    /// assert!(database_url.required());
+    /// ```
+    const FIELDS: &'static [FieldInfo];
+
+    /// Renders a `.env` template listing every variable the struct reads, including those of
+    /// `#[nested]` and `#[enumerated]` fields, under the struct's
+    /// [`ENV_PREFIX`](ConfigStruct::ENV_PREFIX).
+    ///
+    /// Each variable is preceded by the field's `///` documentation and the values an enum
+    /// accepts, leaving out an `Invalid` variant that is only returned when no value matches.
+    /// Required variables are left empty, while variables with a default or that may be
+    /// left unset are commented out, with the default filled in unless the field is secret:
+    ///
+    /// ```rust
+    /// use env_extract::{ConfigStruct, EnvVar};
+    ///
+    /// #[derive(EnvVar)]
+    /// #[var_name = "LOG_LEVEL"]
+    /// #[case(convert = "lowercase")]
+    /// enum LogLevel {
+    ///     Debug,
+    ///     Info,
+    ///     Invalid,
+    /// }
+    ///
+    /// #[derive(ConfigStruct)]
+    /// #[env_prefix = "APP_"]
+    /// struct Config {
+    ///     /// Address to listen on.
+    ///     #[default("0.0.0.0:8080")]
+    ///     listen: String,
+    ///     database_url: String,
+    ///     #[enumerated]
+    ///     log_level: LogLevel,
+    /// }
+    ///
+    /// let example = Config::env_example();
+    /// assert!(example.contains("\nAPP_DATABASE_URL=\n"));
+    /// assert!(example.contains("\n# One of: debug, info\n# APP_LOG_LEVEL=\n"));
+    /// ```
+    ///
+    /// The template for `Config` is:
+    ///
+    /// ```text
+    /// # Address to listen on.
+    /// # APP_LISTEN=0.0.0.0:8080
+    ///
+    /// APP_DATABASE_URL=
+    ///
+    /// # One of: debug, info
+    /// # APP_LOG_LEVEL=
+    /// ```
+    fn env_example() -> String {
+        example::render(Self::FIELDS, Self::ENV_PREFIX)
     }
 
-    let invalid_value = if let Some(v) = default_value {
-        if panic_on_invalid {
-            quote! { panic!("Invalid environment variable value") }
-        } else {
-            quote! { #enum_name::#v }
-        }
-    } else {
-        if panic_on_invalid {
-            quote! { panic!("Invalid environment variable value") }
-        } else {
-            quote! { #enum_name::Invalid }
-        }
-    };
+    /// Builds a JSON Schema for an object holding every variable the struct reads, under the
+    /// struct's [`ENV_PREFIX`](ConfigStruct::ENV_PREFIX), so deployment files can be checked
+    /// without running the program.
+    ///
+    /// Each variable is described with its type, its `///` documentation, its default and the
+    /// checks from `#[validate(...)]`. Variables that must be set are listed as `required`, enum
+    /// fields list the values they accept other than an `Invalid` fallback variant, and secret
+    /// fields are marked `writeOnly` with their default left out. Lists are described as the
+    /// separated string they are read from, so checks on their items are left out. Bounds written
+    /// as something other than a number literal, such as the `Duration` bounds of a duration field,
+    /// cannot be evaluated and are left out as well.
+    ///
+    /// This requires the `json` feature, which is enabled by default.
+    ///
+    /// ```rust
+    /// use env_extract::ConfigStruct;
+    ///
+    /// #[derive(ConfigStruct)]
+    /// #[env_prefix = "APP_"]
+    /// struct Config {
+    ///     /// Port to listen on.
+    ///     #[default(8080)]
+    ///     #[validate(range(min = 1, max = 65535))]
+    ///     port: u16,
+    ///     database_url: String,
+    /// }
+    ///
+    /// let schema = Config::json_schema();
+    /// assert_eq!(schema["required"], serde_json::json!(["APP_DATABASE_URL"]));
+    /// assert_eq!(
+    ///     schema["properties"]["APP_PORT"],
+    ///     serde_json::json!({
+    ///         "type": "integer",
+    ///         "description": "Port to listen on.",
+    ///         "default": 8080,
+    ///         "minimum": 1,
+    ///         "maximum": 65535,
+    ///     })
+    /// );
+    /// ```
+    #[cfg(feature = "json")]
+    fn json_schema() -> serde_json::Value {
+        schema::config_schema(Self::FIELDS, Self::ENV_PREFIX)
+    }
 
-    let expanded = quote! {
-        impl #enum_name {
-            fn get() -> Self {
-                #(#check_variants)*
+    /// Renders a Markdown table listing every variable the struct reads, under the struct's
+    /// [`ENV_PREFIX`](ConfigStruct::ENV_PREFIX), with its type, default, allowed values, `///`
+    /// documentation and whether it is secret.
+    ///
+    /// Required variables have `*required*` in place of a default, and the defaults of secret
+    /// fields are not shown. Allowed values list the values an enum accepts, other than an
+    /// `Invalid` fallback variant, and the checks from `#[validate(...)]`. Bounds that are not
+    /// number literals, such as `Duration::from_secs(1)`, are shown as code.
+    ///
+    /// Checking the table into the project's docs and comparing it in a test catches fields that
+    /// were added or changed without updating them:
+    ///
+    /// ```rust
+    /// use env_extract::ConfigStruct;
+    ///
+    /// #[derive(ConfigStruct)]
+    /// #[env_prefix = "APP_"]
+    /// struct Config {
+    ///     /// Port to listen on.
+    ///     #[default(8080)]
+    ///     #[validate(range(min = 1, max = 65535))]
+    ///     port: u16,
+    ///     #[secret]
+    ///     database_url: String,
+    /// }
+    ///
+    /// assert_eq!(
+    ///     Config::markdown_table(),
+    ///     "\
+    /// | Variable | Type | Default | Allowed values | Description | Secret |
+    /// | --- | --- | --- | --- | --- | --- |
+    /// | `APP_PORT` | integer | `8080` | 1 to 65535 | Port to listen on. | no |
+    /// | `APP_DATABASE_URL` | string | *required* |  |  | yes |
+    /// "
+    /// );
+    /// ```
+    ///
+    /// In a project, the expected table would instead be read with
+    /// `include_str!("../docs/configuration.md")`.
+    fn markdown_table() -> String {
+        markdown::render(Self::FIELDS, Self::ENV_PREFIX)
+    }
 
-                #invalid_value
-            }
+    /// Reports, for every field, the variable that was consulted, whether the value came from it
+    /// or from a `#[default(...)]`, the raw string and the parsed value or error. The values of
+    /// `#[secret]` fields are redacted.
+    ///
+    /// ```rust
+    /// use env_extract::{ConfigStruct, Origin};
+    ///
+    /// #[derive(ConfigStruct)]
+    /// struct Config {
+    ///     #[default(5432)]
+    ///     describe_example_port: u16,
+    ///     #[secret]
+    ///     describe_example_password: String,
+    /// }
+    ///
+    /// fn main() {
+    ///     std::env::remove_var("DESCRIBE_EXAMPLE_PORT");
+    ///     std::env::set_var("DESCRIBE_EXAMPLE_PASSWORD", "hunter2");
+    ///
+    ///     let description = Config::describe();
+    ///     let port = description.field("describe_example_port").unwrap();
+    ///     assert_eq!(port.origin, Origin::Default);
+    ///     assert_eq!(port.result, Ok("5432".to_string()));
+    ///     assert!(!description.to_string().contains("hunter2"));
+    /// }
+    /// ```
+    fn describe() -> Description {
+        Self::describe_from(&Env)
+    }
 
-            fn get_result() -> Result<Self, String> {
-                #(#check_variants_result)*
+    /// Like [`describe`](ConfigStruct::describe), but reads the variables from `source` instead
+    /// of the process environment.
+    fn describe_from(source: &dyn Source) -> Description {
+        let mut reports = Vec::new();
+        let _ = Self::load_with_reports(source, Self::ENV_PREFIX, Some(&mut reports));
+        Description::new(reports)
+    }
 
-                Err("Invalid environment variable value".to_string())
-            }
+    /// Loads every field of the struct from `source` instead of the process environment.
+    fn try_get_from(source: &dyn Source) -> Result<Self, ConfigError> {
+        Self::try_get_from_with_prefix(source, Self::ENV_PREFIX)
+    }
 
-            fn default() -> Self {
-                #invalid_value
-            }
+    /// Like [`try_get_from`](ConfigStruct::try_get_from), but panics if any field could not be
+    /// loaded.
+    fn get_from(source: &dyn Source) -> Self {
+        match Self::try_get_from(source) {
+            Ok(v) => v,
+            Err(e) => panic!("{}", e),
         }
-    };
-
-    TokenStream::from(expanded)
-}
-
-enum CaseConversion {
-    Uppercase,
-    Lowercase,
-    Exact,
-    Any,
-}
+    }
 
-fn get_var_name(attr: &[Attribute]) -> Option<String> {
-    for attr in attr {
-        if let Ok(Meta::NameValue(meta_value)) = attr.parse_meta() {
-            if meta_value.path.is_ident("var_name") {
-                match meta_value.lit {
-                    syn::Lit::Str(ref s) => return Some(s.value()),
-                    _ => panic!("Invalid var_name specified"),
-                }
-            }
-        }
+    /// Loads every field of the struct, returning a [`ConfigError`] that lists all of the fields
+    /// that could not be loaded.
+    fn try_get() -> Result<Self, ConfigError> {
+        Self::try_get_from(&Env)
     }
-    None
-}
 
-fn get_case_conversion(attrs: &[Attribute]) -> (CaseConversion, bool) {
-    for attr in attrs {
-        if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
-            if meta_list.path.is_ident("case") {
-                for nested_meta in meta_list.nested {
-                    if let syn::NestedMeta::Meta(Meta::NameValue(MetaNameValue {
-                        path,
-                        lit: Lit::Str(value),
-                        ..
-                    })) = nested_meta
-                    {
-                        if path.is_ident("convert") {
-                            match value.value().as_str() {
-                                "uppercase" => return (CaseConversion::Uppercase, true),
-                                "lowercase" => return (CaseConversion::Lowercase, true),
-                                "exact" => return (CaseConversion::Exact, true),
-                                "any" => return (CaseConversion::Any, true),
-                                _ => panic!("Invalid case conversion specified"),
-                            }
-                        }
-                    }
-                }
-            }
+    /// Like [`try_get_with_prefix`](ConfigStruct::try_get_with_prefix), but panics if any field
+    /// could not be loaded.
+    fn get_with_prefix(prefix: &str) -> Self {
+        match Self::try_get_with_prefix(prefix) {
+            Ok(v) => v,
+            Err(e) => panic!("{}", e),
         }
     }
 
-    (CaseConversion::Exact, false)
-}
-
-fn get_empty_path_attribute(attrs: &[Attribute], path: &str) -> bool {
-    for attr in attrs {
-        if let Ok(Meta::Path(meta_path)) = attr.parse_meta() {
-            if meta_path.is_ident(path) {
-                return true;
-            }
-        }
+    /// Loads the struct from the process environment and the `.env` file at `path`.
+    ///
+    /// Variables that are set in the process environment take precedence over the file. The file
+    /// is parsed with [`Dotenv`](dotenv::Dotenv) and is never written into the process
+    /// environment.
+    fn from_dotenv(path: impl AsRef<std::path::Path>) -> Result<Self, dotenv::DotenvError> {
+        let dotenv = dotenv::Dotenv::from_path(path)?;
+        Ok(Self::try_get_from(&Env.or(dotenv))?)
     }
-    false
-}
 
-fn get_default_value(attrs: &[Attribute]) -> Option<String> {
-    for attr in attrs {
-        if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
-            if meta_list.path.is_ident("default") {
-                for nested_meta in meta_list.nested {
-                    if let syn::NestedMeta::Lit(Lit::Str(value)) = nested_meta {
-                        return Some(value.value());
-                    }
-                }
+    /// Loads the struct from the process environment and the `.env` file in the current
+    /// directory, if there is one, panicking if the file is invalid or any field could not be
+    /// loaded.
+    ///
+    /// Variables that are set in the process environment take precedence over the file.
+    fn get_with_dotenv() -> Self {
+        match Self::from_dotenv(".env") {
+            Ok(v) => v,
+            Err(dotenv::DotenvError::Io { source, .. })
+                if source.kind() == std::io::ErrorKind::NotFound =>
+            {
+                Self::get()
             }
+            Err(e) => panic!("{}", e),
         }
     }
-    None
-}
-
-#[derive(Debug)]
-
-enum PrimitiveType {
-    String,
-    Number,
-    Bool,
-    ImplementedEnum,
-}
-
-fn get_implemented_enum_ident(ty: &syn::Type) -> String {
-    match ty {
-        syn::Type::Path(type_path) => type_path.clone().into_token_stream().to_string(),
-        _ => panic!("Invalid type"),
-    }
-}
-
-fn get_function_primitive_type(ty: &syn::Type, attributes: &[Attribute]) -> PrimitiveType {
-    match ty {
-        syn::Type::Path(type_path) => {
-            let type_name = match type_path.clone().into_token_stream().to_string() {
-                s if s == "String" => Some(PrimitiveType::String),
-                s if s == "i32"
-                    || s == "u8"
-                    || s == "u16"
-                    || s == "u32"
-                    || s == "u64"
-                    || s == "u128"
-                    || s == "usize"
-                    || s == "i8"
-                    || s == "i16"
-                    || s == "i32"
-                    || s == "i64"
-                    || s == "i128"
-                    || s == "isize"
-                    || s == "f32"
-                    || s == "f64" =>
-                {
-                    Some(PrimitiveType::Number)
-                }
-                s if s == "bool" => Some(PrimitiveType::Bool),
-                _ => None,
-            };
 
-            if let Some(t) = type_name {
-                return t;
-            } else {
-                if let Some(segment) = type_path.clone().path.segments.last() {
-                    if segment.arguments.is_empty() {
-                        if let Some(_attr) = attributes.clone().iter().find(|attr| {
-                            if let Ok(meta) = attr.parse_meta() {
-                                if let syn::Meta::Path(path) = meta {
-                                    path.is_ident("enumerated")
-                                } else {
-                                    false
-                                }
-                            } else {
-                                false
-                            }
-                        }) {
-                            return PrimitiveType::ImplementedEnum;
-                        } else {
-                            panic!("Invalid type")
-                        }
-                    }
-                }
-                panic!("Invalid type")
-            }
+    /// Loads every field of the struct, panicking if any of them could not be loaded.
+    fn get() -> Self {
+        match Self::try_get() {
+            Ok(v) => v,
+            Err(e) => panic!("{}", e),
         }
-        _ => panic!("Invalid type"),
     }
 }
 
-/// This proc macro derives the `ConfigStruct` trait for a struct, enabling easy extraction of
-/// fields from environment variables and parsing them to the correct type.
+/// An enum whose variant can be selected from the value of an environment variable.
 ///
-/// The macro supports the following attributes for struct fields:
-///
-/// - `default`: Sets a default value for the field. If not provided, the macro will panic if the
-///              environment variable is not set.
-/// - `env_var`: Sets the name of the environment variable to use for the field. If not provided,
-///              the macro will use the name of the field in uppercase as the environment variable
-///              name.
-/// - `enumerated`: Identifies an enum that implements the `EnvVar` trait. The macro will parse the
-///                 environment variable to the enum type.
-///
-/// Example usage:
-///
-/// ```rust
-/// use env_extract::ConfigStruct;
-/// use env_extract::EnvVar;
-/// #[derive(Debug, EnvVar)]
-/// #[var_name = "DATABASE_TYPE"]
-/// #[panic_on_invalid]
-/// #[case(convert = "lowercase")]
-/// enum DatabaseType {
-///     Postgres,
-///     Mysql,
-///     Sqlite,
-/// }
-///
-/// #[derive(ConfigStruct, Debug)]
-/// struct Config {
-///     db_host: String,
-///     db_port: u16,
-///     use_tls: bool,
-///
-///     #[enumerated]
-///     db_type: DatabaseType,
-/// }
-///
-/// fn main() {
-///     std::env::set_var("DB_HOST", "localhost");
-///     std::env::set_var("DB_PORT", "5432");
-///     std::env::set_var("USE_TLS", "true");
-///     std::env::set_var("DATABASE_TYPE", "postgres");
-///
-///     let config = Config::get();
-///
-///     assert_eq!(config.db_host, "localhost");
-///     assert_eq!(config.db_port, 5432);
-///     assert_eq!(config.use_tls, true);
-///     assert!(matches!(config.db_type, DatabaseType::Postgres));
-/// }
-/// ```
-///
-/// In the example above, the `ConfigStruct` trait is derived for the `Config` struct, allowing
-/// easy extraction of fields from environment variables. The `db_host`, `db_port`, and `use_tls`
-/// fields are extracted as `String`, `u16`, and `bool` types, respectively. The `db_type` field is
-/// extracted as an enum type `DatabaseType`, which is parsed from the environment variable named
-/// `DATABASE_TYPE` and converted to lowercase.
-#[proc_macro_derive(ConfigStruct, attributes(default, enumerated, var_name))]
-pub fn env_for_struct(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-
-    let struct_name = &input.ident;
-    let fields = match input.data {
-        syn::Data::Struct(s) => s.fields,
-        _ => panic!("StructVar only supports structs."),
-    };
-
-    let mut check_fields = Vec::new();
-    for field in fields {
-        let field_type = get_function_primitive_type(&field.ty, &field.attrs);
-        let field_ident = field.ident.unwrap();
-
-        let default_value_or_panic = match get_default_value(&field.attrs) {
-            Some(v) => match field_type {
-                PrimitiveType::String => quote! { #v.to_string() },
-                PrimitiveType::Number => quote! { #v.to_string().parse().unwrap() },
-                PrimitiveType::Bool => quote! { #v.to_string().parse().unwrap() },
-                PrimitiveType::ImplementedEnum => quote! {},
-            },
-            None => {
-                quote! { panic!("No environment variable or default value found for '{}'", stringify!(#field_ident)) }
-            }
-        };
-
-        let var_name_to_check_for = match get_var_name(&field.attrs) {
-            Some(v) => v,
-            None => field_ident.to_token_stream().to_string().to_uppercase(),
-        };
-
-        let enum_ident: syn::Ident;
-        match field_type {
-            PrimitiveType::ImplementedEnum => {
-                enum_ident =
-                    syn::parse_str(&get_implemented_enum_ident(&field.ty).as_str()).unwrap()
-            }
-            _ => enum_ident = field_ident.clone(),
-        };
-
-        check_fields.push(match field_type {
-            PrimitiveType::Bool => quote! {
-                 #field_ident: match std::env::var(#var_name_to_check_for) {
-                    Ok(v) => match v.to_string().parse() {
-                        Ok(v) => v,
-                        Err(..) => false
-                    },
-                    Err(..) => false
-                 },
-            },
-            PrimitiveType::String => quote! {
-                 #field_ident: match std::env::var(#var_name_to_check_for) {
-                    Ok(v) => v.to_string(),
-                    Err(..) => #default_value_or_panic
-                 },
-            },
-            PrimitiveType::ImplementedEnum => quote! {
-                #field_ident: match #enum_ident::get_result() {
-                    Ok(v) => v,
-                    Err(e) =>  #enum_ident::default()
-                },
-            },
-            PrimitiveType::Number => quote! {
-                 #field_ident: match std::env::var(#var_name_to_check_for) {
-                    Ok(v) => match v.to_string().trim().parse() {
-                        Ok(v) => v,
-                        Err(..) => #default_value_or_panic
-                    },
-                    Err(..) => #default_value_or_panic
-                 },
-            },
-        });
+/// This trait is implemented with `#[derive(EnvVar)]`.
+pub trait EnvVar: Sized {
+    /// The name of the environment variable the enum is read from.
+    const VAR_NAME: &'static str;
+
+    /// Older names of the variable, read in order when [`VAR_NAME`](EnvVar::VAR_NAME) is not set.
+    const VAR_ALIASES: &'static [&'static str] = &[];
+
+    /// Describes the enum's variants in declaration order, with the value that selects each one.
+    /// Every variant without `#[ignore_variant]` is listed, including an `Invalid` variant, which
+    /// is selected by its own name like any other. The variant returned when no value matches is
+    /// marked as the [`fallback`](VariantInfo::fallback). An `Invalid` fallback variant is left out
+    /// of the allowed values in `env_example()`, `json_schema()` and `markdown_table()`.
+    const VARIANTS: &'static [VariantInfo];
+
+    /// Returns the variant matching the environment variable, falling back to the enum's invalid
+    /// value if no variant matches.
+    fn get() -> Self {
+        Self::get_from(&Env)
     }
 
-    let expanded = quote! {
-        impl #struct_name {
-            pub fn get() -> Self {
-                Self {
-                    #(#check_fields)*
-                }
-            }
-        }
-    };
+    /// Returns the variant matching the environment variable, or an [`EnvVarError`] describing
+    /// why no variant could be selected.
+    fn get_result() -> Result<Self, EnvVarError> {
+        Self::try_get_from(&Env)
+    }
 
-    expanded.into()
+    /// Like [`get`](EnvVar::get), but reads the variable from `source` instead of the process
+    /// environment.
+    fn get_from(source: &dyn Source) -> Self;
+
+    /// Like [`get_result`](EnvVar::get_result), but reads the variable from `source` instead of
+    /// the process environment.
+    fn try_get_from(source: &dyn Source) -> Result<Self, EnvVarError>;
+
+    /// Returns the enum's fallback value: the `#[default]` variant, the `Invalid` variant, or a
+    /// panic if the enum is marked with `#[panic_on_invalid]`.
+    ///
+    /// This is not named `default` so that it does not clash with `Default::default` on enums
+    /// that also derive `Default`.
+    fn fallback() -> Self;
+
+    /// Builds a JSON Schema for an object holding the enum's variable, listing the values that
+    /// select a variant after case conversion, other than an `Invalid` fallback variant. Values
+    /// that are compared without regard to case are matched with a `pattern` instead of an `enum`.
+    /// The variable is only `required` when the enum has no `#[default]` or `Invalid` variant to
+    /// fall back to.
+    ///
+    /// This requires the `json` feature, which is enabled by default.
+    #[cfg(feature = "json")]
+    fn json_schema() -> serde_json::Value {
+        schema::enum_schema(Self::VAR_NAME, Self::VARIANTS)
+    }
 }
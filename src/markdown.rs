@@ -0,0 +1,165 @@
+//! Markdown tables documenting the variables read by a `ConfigStruct`.
+
+use crate::{Constraint, FieldInfo, FieldKind};
+
+/// Renders a table with one row per variable in `fields`, read under `prefix`.
+pub(crate) fn render(fields: &[FieldInfo], prefix: &str) -> String {
+    let mut out = String::from(
+        "| Variable | Type | Default | Allowed values | Description | Secret |\n\
+         | --- | --- | --- | --- | --- | --- |\n",
+    );
+    write_rows(&mut out, fields, prefix);
+    out
+}
+
+fn write_rows(out: &mut String, fields: &[FieldInfo], prefix: &str) {
+    for field in fields {
+        let var_name = field.var_name_with_prefix(prefix);
+        if let FieldKind::Nested { fields } = field.kind {
+            write_rows(out, fields, &var_name);
+            continue;
+        }
+
+        let default = match field.default {
+            _ if field.required() => "*required*".to_string(),
+            Some(_) if field.secret => "*redacted*".to_string(),
+            Some(default) => code(default),
+            None if matches!(field.kind, FieldKind::Bool) && !field.optional => code("false"),
+            None => String::new(),
+        };
+
+        let mut description: Vec<String> = field
+            .doc
+            .split("\n\n")
+            .map(|paragraph| paragraph.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|paragraph| !paragraph.is_empty())
+            .collect();
+        let aliases = field.aliases_with_prefix(prefix);
+        if !aliases.is_empty() {
+            let aliases: Vec<String> = aliases.iter().map(|alias| code(alias)).collect();
+            description.push(format!("Also read from {}.", aliases.join(", ")));
+        }
+        if field.from_file {
+            description.push(format!(
+                "Can be read from the file named by {}.",
+                code(&format!("{}_FILE", var_name))
+            ));
+        }
+
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            code(&var_name),
+            type_name(&field.kind, field.ty),
+            default,
+            escape(&allowed_values(&field.kind, field.constraints)),
+            escape(&description.join("<br><br>")),
+            if field.secret { "yes" } else { "no" },
+        ));
+    }
+}
+
+fn type_name(kind: &FieldKind, ty: &str) -> String {
+    match kind {
+        FieldKind::String => "string".to_string(),
+        FieldKind::Integer => "integer".to_string(),
+        FieldKind::Float => "number".to_string(),
+        FieldKind::Bool => "boolean".to_string(),
+        FieldKind::Bytes => "byte size".to_string(),
+        FieldKind::Duration { .. } => "duration".to_string(),
+        FieldKind::FromStr => code(&inner_type(ty).split_whitespace().collect::<String>()),
+        FieldKind::List { item, separator } => format!(
+            "list of {}, separated by {}",
+            type_name(item, inner_type(ty)),
+            code(separator)
+        ),
+        FieldKind::Enum { .. } => "enum".to_string(),
+        FieldKind::Nested { .. } => "struct".to_string(),
+    }
+}
+
+/// Strips `Option`, `Secret` and list wrappers from a type as written by `stringify!`, so
+/// `Option<PathBuf>` is shown as `PathBuf`.
+fn inner_type(ty: &str) -> &str {
+    let ty = ty.trim();
+    match (ty.find('<'), ty.ends_with('>')) {
+        (Some(start), true) => inner_type(&ty[start + 1..ty.len() - 1]),
+        _ => ty,
+    }
+}
+
+fn allowed_values(kind: &FieldKind, constraints: &[Constraint]) -> String {
+    let (items, count) = match kind {
+        FieldKind::List { .. } => ("each item ", "items"),
+        _ => ("", "characters"),
+    };
+
+    let mut allowed = Vec::new();
+    if let FieldKind::Enum { variants }
+    | FieldKind::List {
+        item: FieldKind::Enum { variants },
+        ..
+    } = kind
+    {
+        let variants: Vec<_> = variants
+            .iter()
+            .filter(|variant| !variant.is_invalid_fallback())
+            .collect();
+        let values: Vec<String> = variants.iter().map(|variant| code(variant.value)).collect();
+        allowed.push(format!("{}{}", items, values.join(", ")));
+        if variants.iter().any(|variant| variant.any_case) {
+            allowed.push("in any case".to_string());
+        }
+    }
+    for constraint in constraints {
+        match constraint {
+            Constraint::Range { min, max } => {
+                allowed.push(format!("{}{}", items, bounds(min, max, "")));
+            }
+            Constraint::Length { min, max } => allowed.push(bounds(min, max, count)),
+            Constraint::NonEmpty => allowed.push("not empty".to_string()),
+            Constraint::Regex(pattern) => {
+                allowed.push(format!("{}matching {}", items, code(pattern)))
+            }
+            Constraint::OneOf(values) => {
+                let values: Vec<String> = values.iter().map(|value| code(value)).collect();
+                allowed.push(format!("{}{}", items, values.join(", ")));
+            }
+            Constraint::With(path) => allowed.push(format!("checked by {}", code(path))),
+        }
+    }
+    allowed.join("; ")
+}
+
+fn bounds(min: &Option<&str>, max: &Option<&str>, unit: &str) -> String {
+    let (min, max) = (min.map(bound), max.map(bound));
+    let bounds = match (min, max) {
+        (Some(min), Some(max)) => format!("{} to {}", min, max),
+        (Some(min), None) => format!("at least {}", min),
+        (None, Some(max)) => format!("at most {}", max),
+        (None, None) => String::new(),
+    };
+    if unit.is_empty() {
+        bounds
+    } else {
+        format!("{} {}", bounds, unit)
+    }
+}
+
+/// Formats a bound written as an expression other than a number literal, such as
+/// `Duration::from_secs(1)`, as code.
+fn bound(value: &str) -> String {
+    if value.replace('_', "").parse::<f64>().is_ok() {
+        value.to_string()
+    } else {
+        code(value)
+    }
+}
+
+fn code(value: &str) -> String {
+    format!("`{}`", value)
+}
+
+/// Escapes the characters that would end a table cell.
+fn escape(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
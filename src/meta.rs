@@ -0,0 +1,142 @@
+//! Static descriptions of derived types, exposed as `ConfigStruct::FIELDS` and
+//! `EnvVar::VARIANTS`.
+
+/// A field of a struct deriving `ConfigStruct`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldInfo {
+    /// The name of the struct field.
+    pub name: &'static str,
+    /// The variable the field is read from. For `#[nested]` fields this is the prefix placed in
+    /// front of the nested struct's variables.
+    pub var_name: &'static str,
+    /// Whether the struct's prefix is placed in front of `var_name`. This is `false` for names set
+    /// with `#[var_name]`.
+    pub prefixed: bool,
+    /// Older names that are read when `var_name` is not set. Only the aliases of an
+    /// `#[enumerated]` field's enum are read under the struct's prefix.
+    pub aliases: &'static [&'static str],
+    /// The field's type as written in the struct.
+    pub ty: &'static str,
+    /// The kind of value the field holds.
+    pub kind: FieldKind,
+    /// Whether the field is an `Option`.
+    pub optional: bool,
+    /// The value set with `#[default(...)]`.
+    pub default: Option<&'static str>,
+    /// The field's `///` documentation, without the leading `///`.
+    pub doc: &'static str,
+    /// Whether the field is marked with `#[secret]` or is a `Secret<T>`.
+    pub secret: bool,
+    /// Whether the field can be read from the file named by `<VAR>_FILE`.
+    pub from_file: bool,
+    /// The checks from the field's `#[validate(...)]` attributes.
+    pub constraints: &'static [Constraint],
+}
+
+impl FieldInfo {
+    /// Returns the full name of the variable, read under `prefix`.
+    pub fn var_name_with_prefix(&self, prefix: &str) -> String {
+        if self.prefixed {
+            format!("{}{}", prefix, self.var_name)
+        } else {
+            self.var_name.to_string()
+        }
+    }
+
+    /// Returns the full names of the field's aliases, read under `prefix`.
+    pub fn aliases_with_prefix(&self, prefix: &str) -> Vec<String> {
+        self.aliases
+            .iter()
+            .map(|alias| match self.kind {
+                FieldKind::Enum { .. } => format!("{}{}", prefix, alias),
+                _ => alias.to_string(),
+            })
+            .collect()
+    }
+
+    /// Whether loading fails when the field's variable is not set.
+    pub fn required(&self) -> bool {
+        let has_fallback = match self.kind {
+            FieldKind::Bool => true,
+            FieldKind::Enum { variants } => crate::__private::has_fallback(variants),
+            _ => false,
+        };
+        !self.optional && self.default.is_none() && !has_fallback
+    }
+}
+
+/// The kind of value a [`FieldInfo`] holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// A `String`.
+    String,
+    /// An integer.
+    Integer,
+    /// An `f32` or `f64`.
+    Float,
+    /// A `bool`.
+    Bool,
+    /// An integer read as a byte size with `#[bytes]`.
+    Bytes,
+    /// A `Duration`, with the unit used for bare numbers.
+    Duration { unit: &'static str },
+    /// A type parsed with its `FromStr` implementation.
+    FromStr,
+    /// A `Vec`, `HashSet` or `BTreeSet`, with the kind of its items and the separator between them.
+    List {
+        item: &'static FieldKind,
+        separator: &'static str,
+    },
+    /// An `#[enumerated]` enum deriving `EnvVar`.
+    Enum { variants: &'static [VariantInfo] },
+    /// A `#[nested]` struct deriving `ConfigStruct`.
+    Nested { fields: &'static [FieldInfo] },
+}
+
+/// A check from a field's `#[validate(...)]` attribute. Bounds are kept as the expressions written
+/// in the attribute, such as `Duration::from_secs(1)`, since they may be any expression of the
+/// field's type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    /// `range(min = ..., max = ...)`.
+    Range {
+        min: Option<&'static str>,
+        max: Option<&'static str>,
+    },
+    /// `length(min = ..., max = ...)`.
+    Length {
+        min: Option<&'static str>,
+        max: Option<&'static str>,
+    },
+    /// `non_empty`.
+    NonEmpty,
+    /// `regex = "..."`, with the pattern.
+    Regex(&'static str),
+    /// `one_of(...)`, with the allowed values.
+    OneOf(&'static [&'static str]),
+    /// `with = path`, with the path of the function.
+    With(&'static str),
+}
+
+/// A variant of an enum deriving `EnvVar`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VariantInfo {
+    /// The name of the variant.
+    pub name: &'static str,
+    /// The value that selects the variant, after case conversion.
+    pub value: &'static str,
+    /// Whether the value is compared without regard to case.
+    pub any_case: bool,
+    /// Whether the variant is returned when the variable is not set or matches no variant: the
+    /// variant marked with `#[default]`, or else the `Invalid` variant. No variant is the fallback
+    /// of an enum marked with `#[panic_on_invalid]`.
+    pub fallback: bool,
+}
+
+impl VariantInfo {
+    /// Whether the variant is an `Invalid` variant that is only returned when no value matches, so
+    /// it is left out of the values that generated documentation lists as allowed.
+    pub(crate) fn is_invalid_fallback(&self) -> bool {
+        self.fallback && self.name == "Invalid"
+    }
+}
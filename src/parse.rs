@@ -0,0 +1,208 @@
+//! Parsing helpers used by the code generated by `#[derive(ConfigStruct)]`.
+//!
+//! These are public so that the same rules can be applied to values read outside of a derived
+//! struct.
+
+use std::time::Duration;
+
+/// Splits a delimited list value, such as `a,b,c`, into its items.
+///
+/// When `trim` is set, whitespace around each item is removed. When `skip_empty` is set, items that
+/// are empty (after trimming) are dropped, so `a,,b,` yields two items and an empty value yields
+/// none.
+///
+/// ```rust
+/// use env_extract::parse::split_list;
+///
+/// let items: Vec<&str> = split_list(" a, b ,,c", ",", true, true).collect();
+/// assert_eq!(items, ["a", "b", "c"]);
+///
+/// let items: Vec<&str> = split_list("a;;b", ";", false, false).collect();
+/// assert_eq!(items, ["a", "", "b"]);
+/// ```
+pub fn split_list<'a>(
+    value: &'a str,
+    separator: &'a str,
+    trim: bool,
+    skip_empty: bool,
+) -> impl Iterator<Item = &'a str> + 'a {
+    value
+        .split(separator)
+        .map(move |item| if trim { item.trim() } else { item })
+        .filter(move |item| !(skip_empty && item.is_empty()))
+}
+
+/// Parses a duration such as `30s`, `500ms`, `1h30m` or `2d`.
+///
+/// A value is a sequence of whole numbers, each followed by one of the units `ns`, `us` (or `µs`),
+/// `ms`, `s`, `m`, `h` or `d`, with optional spaces between them. A number without a unit is read in
+/// `default_unit`. Durations that do not fit in a [`Duration`] are reported as errors.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use env_extract::parse::duration;
+///
+/// assert_eq!(duration("1h30m", "s"), Ok(Duration::from_secs(5400)));
+/// assert_eq!(duration("500ms", "s"), Ok(Duration::from_millis(500)));
+/// assert_eq!(duration("250", "ms"), Ok(Duration::from_millis(250)));
+/// assert!(duration("30 parsecs", "s").is_err());
+/// ```
+pub fn duration(value: &str, default_unit: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("expected a duration such as 30s or 1h30m".to_string());
+    }
+
+    if value.bytes().all(|b| b.is_ascii_digit()) {
+        return duration_in_unit(parse_component(value)?, default_unit);
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            return Err(format!("expected a number at {:?}", rest));
+        }
+        let amount = parse_component(&rest[..digits])?;
+        rest = rest[digits..].trim_start();
+
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c.is_whitespace())
+            .unwrap_or(rest.len());
+        if unit_len == 0 {
+            return Err(format!(
+                "missing unit after {}, expected one of ns, us, ms, s, m, h or d",
+                amount
+            ));
+        }
+        let component = duration_in_unit(amount, &rest[..unit_len])?;
+        total = total
+            .checked_add(component)
+            .ok_or_else(|| "duration is too large".to_string())?;
+        rest = rest[unit_len..].trim_start();
+    }
+    Ok(total)
+}
+
+fn parse_component(digits: &str) -> Result<u64, String> {
+    digits
+        .parse()
+        .map_err(|_| format!("{} is too large", digits))
+}
+
+fn duration_in_unit(amount: u64, unit: &str) -> Result<Duration, String> {
+    let seconds_per_unit = match unit {
+        "ns" => return Ok(Duration::from_nanos(amount)),
+        "us" | "µs" => return Ok(Duration::from_micros(amount)),
+        "ms" => return Ok(Duration::from_millis(amount)),
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => {
+            return Err(format!(
+                "unknown unit {:?}, expected one of ns, us, ms, s, m, h or d",
+                unit
+            ))
+        }
+    };
+    amount
+        .checked_mul(seconds_per_unit)
+        .map(Duration::from_secs)
+        .ok_or_else(|| "duration is too large".to_string())
+}
+
+/// Parses a byte size such as `512MiB`, `2GB` or `1.5k`.
+///
+/// The number may be followed by an SI suffix (`k`, `M`, `G`, `T`, `P`, `E`, optionally followed by
+/// `B`), which multiplies it by a power of 1000, or an IEC suffix (`Ki`, `Mi`, `Gi`, ..., optionally
+/// followed by `B`), which multiplies it by a power of 1024. Suffixes are case-insensitive and may
+/// be separated from the number by spaces. A bare number or a `B` suffix is a number of bytes.
+///
+/// Fractions are accepted as long as they add up to a whole number of bytes. Sizes that do not fit
+/// in a `u128` are reported as errors.
+///
+/// ```rust
+/// use env_extract::parse::bytes;
+///
+/// assert_eq!(bytes("512MiB"), Ok(512 * 1024 * 1024));
+/// assert_eq!(bytes("2GB"), Ok(2_000_000_000));
+/// assert_eq!(bytes("1.5 KiB"), Ok(1536));
+/// assert_eq!(bytes("4096"), Ok(4096));
+/// assert!(bytes("12 parsecs").is_err());
+/// ```
+pub fn bytes(value: &str) -> Result<u128, String> {
+    let value = value.trim();
+    let number_len = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, suffix) = value.split_at(number_len);
+
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if whole.is_empty() && fraction.is_empty() || fraction.contains('.') {
+        return Err("expected a size such as 512MiB or 2GB".to_string());
+    }
+
+    let multiplier = byte_multiplier(suffix.trim_start())?;
+    let too_large = || format!("{} is too large", value);
+
+    // Compute `whole.fraction * multiplier` exactly, as `digits * multiplier / 10^fraction_len`.
+    let digits: u128 = format!("{}{}", whole, fraction)
+        .parse()
+        .map_err(|_| too_large())?;
+    let scale = 10u128
+        .checked_pow(fraction.len() as u32)
+        .ok_or_else(too_large)?;
+    let scaled = digits.checked_mul(multiplier).ok_or_else(too_large)?;
+    if scaled % scale != 0 {
+        return Err(format!("{} is not a whole number of bytes", value));
+    }
+    Ok(scaled / scale)
+}
+
+fn byte_multiplier(suffix: &str) -> Result<u128, String> {
+    let suffix = suffix.to_ascii_lowercase();
+    let unit = suffix.strip_suffix('b').unwrap_or(&suffix);
+    let (prefix, binary) = match unit.strip_suffix('i') {
+        Some(prefix) if !prefix.is_empty() => (prefix, true),
+        _ => (unit, false),
+    };
+
+    let exponent = match prefix {
+        "" => 0,
+        "k" => 1,
+        "m" => 2,
+        "g" => 3,
+        "t" => 4,
+        "p" => 5,
+        "e" => 6,
+        _ => {
+            return Err(format!(
+                "unknown size suffix {:?}, expected a suffix such as k, KB, KiB, M or GiB",
+                suffix
+            ))
+        }
+    };
+    let base: u128 = if binary { 1024 } else { 1000 };
+    Ok(base.pow(exponent))
+}
+
+/// Parses a boolean, accepting `true`/`false`, `yes`/`no`, `on`/`off` and `1`/`0` in any case.
+///
+/// ```rust
+/// use env_extract::parse::boolean;
+///
+/// assert_eq!(boolean("Yes"), Ok(true));
+/// assert_eq!(boolean(" off "), Ok(false));
+/// assert!(boolean("maybe").is_err());
+/// ```
+pub fn boolean(value: &str) -> Result<bool, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Ok(true),
+        "false" | "no" | "off" | "0" => Ok(false),
+        _ => Err("expected one of true, false, yes, no, on, off, 1 or 0".to_string()),
+    }
+}
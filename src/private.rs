@@ -0,0 +1,215 @@
+//! Helpers used by the code that the derives generate. Nothing in this module is part of the
+//! public API.
+
+use std::collections::HashMap;
+use std::mem;
+#[cfg(feature = "regex")]
+use std::sync::OnceLock;
+
+use zeroize::Zeroize;
+
+use crate::{DeprecatedVar, EnvVar, EnvVarError, Origin, Source, VariantInfo};
+
+/// Reads `var_name` from `source`. When `from_file` is set and `<var_name>_FILE` is set instead,
+/// the value is read from the file it names, without the trailing newline.
+pub fn lookup(
+    source: &dyn Source,
+    var_name: &str,
+    from_file: bool,
+) -> Result<Option<(String, Origin)>, String> {
+    let value = source.get(var_name);
+    if !from_file {
+        return Ok(value.map(|v| (v, Origin::Variable)));
+    }
+
+    let file_var = format!("{}_FILE", var_name);
+    match (value, source.get(&file_var)) {
+        (Some(_), Some(_)) => Err(format!(
+            "both {} and {} are set, only one of them may be used",
+            var_name, file_var
+        )),
+        (Some(v), None) => Ok(Some((v, Origin::Variable))),
+        (None, Some(path)) => match std::fs::read_to_string(&path) {
+            Ok(contents) => {
+                let len = contents.trim_end_matches(['\n', '\r']).len();
+                let mut contents = contents;
+                contents.truncate(len);
+                Ok(Some((contents, Origin::File)))
+            }
+            Err(e) => Err(format!("could not read {} ({}): {}", file_var, path, e)),
+        },
+        (None, None) => Ok(None),
+    }
+}
+
+/// Like [`lookup`], but falls back to each of `aliases` in order when `var_name` is not set.
+/// Returns the name the value was read from, or `var_name` if none of them were set. Reading a
+/// `deprecated` alias is reported through the deprecation hook.
+pub fn lookup_aliased(
+    source: &dyn Source,
+    var_name: String,
+    aliases: &[&str],
+    deprecated: bool,
+    from_file: bool,
+) -> (String, Result<Option<(String, Origin)>, String>) {
+    let found = lookup(source, &var_name, from_file);
+    if !matches!(found, Ok(None)) {
+        return (var_name, found);
+    }
+
+    for alias in aliases {
+        let found = lookup(source, alias, from_file);
+        if !matches!(found, Ok(None)) {
+            if deprecated {
+                crate::deprecated::warn(&DeprecatedVar {
+                    name: alias.to_string(),
+                    replacement: var_name,
+                });
+            }
+            return (alias.to_string(), found);
+        }
+    }
+    (var_name, Ok(None))
+}
+
+/// Returns the name and value of the first of `var_name` and `aliases` that is set in `source`
+/// under `prefix`, for reporting on `#[enumerated]` fields.
+pub fn lookup_enum(
+    source: &dyn Source,
+    prefix: &str,
+    var_name: &str,
+    aliases: &[&str],
+) -> (String, Option<String>) {
+    std::iter::once(var_name)
+        .chain(aliases.iter().copied())
+        .find_map(|name| {
+            let name = format!("{}{}", prefix, name);
+            source.get(&name).map(|value| (name, Some(value)))
+        })
+        .unwrap_or_else(|| (format!("{}{}", prefix, var_name), None))
+}
+
+/// Reads an `#[enumerated]` field's enum from `source` under `prefix`, the prefix of the struct
+/// holding the field, so the same struct can be nested under several prefixes.
+pub fn get_enum<E: EnvVar>(source: &dyn Source, prefix: &str) -> Result<E, EnvVarError> {
+    E::try_get_from(&Prefixed { source, prefix }).map_err(|error| match error {
+        EnvVarError::Missing { var_name } => EnvVarError::Missing {
+            var_name: format!("{}{}", prefix, var_name),
+        },
+        EnvVarError::Invalid {
+            var_name,
+            value,
+            expected,
+        } => EnvVarError::Invalid {
+            var_name: format!("{}{}", prefix, var_name),
+            value,
+            expected,
+        },
+    })
+}
+
+/// A source that reads every key of `source` under `prefix`.
+struct Prefixed<'a> {
+    source: &'a dyn Source,
+    prefix: &'a str,
+}
+
+impl Source for Prefixed<'_> {
+    fn get(&self, key: &str) -> Option<String> {
+        self.source.get(&format!("{}{}", self.prefix, key))
+    }
+}
+
+/// Whether an `#[enumerated]` field falls back to one of `variants` when its variable is missing
+/// or matches none of them, rather than failing to load.
+pub fn has_fallback(variants: &[VariantInfo]) -> bool {
+    variants.iter().any(|variant| variant.fallback)
+}
+
+/// Compares a loaded field with the value given in `#[required_if(field = value)]`.
+pub fn equals<T: PartialEq<U> + ?Sized, U>(value: &T, expected: &U) -> bool {
+    value == expected
+}
+
+/// Whether `value`, a loaded `#[enumerated]` field, is the variant that `expected` selects in
+/// `#[required_if(field = "expected")]`. A value that selects no variant never matches.
+pub fn is_variant<E: EnvVar>(value: &E, expected: &str) -> bool {
+    let source = HashMap::from([(E::VAR_NAME, expected)]);
+    E::try_get_from(&source)
+        .is_ok_and(|expected| mem::discriminant(value) == mem::discriminant(&expected))
+}
+
+/// Parses an item of an `#[enumerated]` list into the variant it selects. Unlike the enum's own
+/// variable, an item that selects no variant is an error rather than the fallback variant.
+pub fn parse_variant<E: EnvVar>(item: &str) -> Result<E, String> {
+    let source = HashMap::from([(E::VAR_NAME, item)]);
+    E::try_get_from(&source).map_err(|_| {
+        let values: Vec<&str> = E::VARIANTS
+            .iter()
+            .filter(|variant| !variant.is_invalid_fallback())
+            .map(|variant| variant.value)
+            .collect();
+        format!("expected one of {}", values.join(", "))
+    })
+}
+
+/// The compiled pattern of a `#[validate(regex = "...")]` check, kept in a `static` of the
+/// generated code so the pattern is compiled once rather than on every load.
+#[cfg(feature = "regex")]
+pub type CompiledRegex = OnceLock<Result<regex::Regex, String>>;
+
+/// Checks `value` against `pattern` like [`validate::regex`](crate::validate::regex), compiling
+/// the pattern into `compiled` the first time it is used.
+#[cfg(feature = "regex")]
+pub fn regex(value: &str, pattern: &str, compiled: &CompiledRegex) -> Result<(), String> {
+    match compiled.get_or_init(|| crate::validate::compile(pattern)) {
+        Ok(regex) => crate::validate::is_match(value, pattern, regex),
+        Err(message) => Err(message.clone()),
+    }
+}
+
+/// Wipes the raw value of a secret field, leaving a placeholder in its place.
+pub fn redact(raw: Option<String>) -> Option<String> {
+    raw.map(|mut raw| {
+        raw.zeroize();
+        "<redacted>".to_string()
+    })
+}
+
+/// Replaces the message of a parse error on a secret field, since parsers often quote the value
+/// or the item that could not be parsed.
+pub fn redact_message(mut message: String) -> String {
+    message.zeroize();
+    "could not be parsed, the details are hidden because the field is secret".to_string()
+}
+
+/// Replaces the message of a failed `#[validate(...)]` check on a secret field, since it may give
+/// away the value's length or quote the value.
+pub fn redact_check_message(mut message: String) -> String {
+    message.zeroize();
+    "failed validation, the details are hidden because the field is secret".to_string()
+}
+
+/// Formats values for [`FieldReport::result`](crate::FieldReport::result) with `Debug` when the
+/// field type implements it.
+pub struct Describe<'a, T>(pub &'a T);
+
+pub trait DescribeDebug {
+    fn describe_value(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> DescribeDebug for Describe<'_, T> {
+    fn describe_value(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+pub trait DescribeOther {
+    fn describe_value(&self) -> String;
+}
+
+impl<T> DescribeOther for &Describe<'_, T> {
+    fn describe_value(&self) -> String {
+        "<no Debug implementation>".to_string()
+    }
+}
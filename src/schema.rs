@@ -0,0 +1,216 @@
+//! JSON Schemas describing the variables read by derived types.
+
+use serde_json::{json, Map, Value};
+
+use crate::{Constraint, FieldInfo, FieldKind, VariantInfo};
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Builds the schema of an object holding every variable in `fields`, read under `prefix`.
+pub(crate) fn config_schema(fields: &[FieldInfo], prefix: &str) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    add_fields(&mut properties, &mut required, fields, prefix);
+
+    json!({
+        "$schema": DRAFT,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+/// Builds the schema of an object holding the variable an `EnvVar` enum is read from. The variable
+/// is only required when the enum has no fallback variant to use when it is missing.
+pub(crate) fn enum_schema(var_name: &str, variants: &[VariantInfo]) -> Value {
+    let mut properties = Map::new();
+    properties.insert(
+        var_name.to_string(),
+        Value::Object(variants_schema(variants)),
+    );
+    let required: &[&str] = if crate::__private::has_fallback(variants) {
+        &[]
+    } else {
+        &[var_name]
+    };
+
+    json!({
+        "$schema": DRAFT,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+fn add_fields(
+    properties: &mut Map<String, Value>,
+    required: &mut Vec<String>,
+    fields: &[FieldInfo],
+    prefix: &str,
+) {
+    for field in fields {
+        let var_name = field.var_name_with_prefix(prefix);
+        if let FieldKind::Nested { fields } = field.kind {
+            add_fields(properties, required, fields, &var_name);
+            continue;
+        }
+
+        let mut schema = kind_schema(&field.kind);
+        add_constraints(&mut schema, &field.kind, field.constraints);
+        let mut description = field.doc.to_string();
+        if let FieldKind::List { separator, .. } = field.kind {
+            if !description.is_empty() {
+                description.push_str("\n\n");
+            }
+            description.push_str(&format!("Items are separated by {:?}.", separator));
+        }
+        if !description.is_empty() {
+            schema.insert("description".to_string(), json!(description));
+        }
+        match field.default {
+            Some(default) if !field.secret => {
+                schema.insert("default".to_string(), default_value(&field.kind, default));
+            }
+            _ => {}
+        }
+        if field.secret {
+            schema.insert("writeOnly".to_string(), json!(true));
+        }
+
+        if field.required() {
+            required.push(var_name.clone());
+        }
+        properties.insert(var_name, Value::Object(schema));
+    }
+}
+
+fn kind_schema(kind: &FieldKind) -> Map<String, Value> {
+    let schema = match kind {
+        // Lists are read from a single variable holding the separated items.
+        FieldKind::String | FieldKind::FromStr | FieldKind::List { .. } => {
+            json!({ "type": "string" })
+        }
+        FieldKind::Integer => json!({ "type": "integer" }),
+        FieldKind::Float => json!({ "type": "number" }),
+        FieldKind::Bool => json!({ "type": "boolean" }),
+        FieldKind::Bytes => json!({ "type": ["integer", "string"], "format": "bytes" }),
+        FieldKind::Duration { .. } => {
+            json!({ "type": ["integer", "string"], "format": "duration" })
+        }
+        FieldKind::Enum { variants } => return variants_schema(variants),
+        FieldKind::Nested { .. } => json!({ "type": "object" }),
+    };
+    match schema {
+        Value::Object(schema) => schema,
+        _ => unreachable!(),
+    }
+}
+
+/// Lists the values that select a variant, other than an `Invalid` fallback variant. Values
+/// compared without regard to case are matched with a pattern instead, since `enum` is
+/// case-sensitive.
+fn variants_schema(variants: &[VariantInfo]) -> Map<String, Value> {
+    let variants: Vec<&VariantInfo> = variants
+        .iter()
+        .filter(|variant| !variant.is_invalid_fallback())
+        .collect();
+    let mut schema = Map::new();
+    schema.insert("type".to_string(), json!("string"));
+    if variants.iter().any(|variant| variant.any_case) {
+        let alternatives: Vec<String> = variants
+            .iter()
+            .map(|variant| {
+                if variant.any_case {
+                    any_case_pattern(variant.value)
+                } else {
+                    variant.value.to_string()
+                }
+            })
+            .collect();
+        schema.insert(
+            "pattern".to_string(),
+            json!(format!("^(?:{})$", alternatives.join("|"))),
+        );
+    } else {
+        let values: Vec<&str> = variants.iter().map(|variant| variant.value).collect();
+        schema.insert("enum".to_string(), json!(values));
+    }
+    schema
+}
+
+fn any_case_pattern(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_alphabetic() {
+                format!("[{}{}]", c.to_lowercase(), c.to_uppercase())
+            } else {
+                c.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Adds the checks that apply to the whole value. The checks on a list apply to the items or to
+/// their number, which a schema for the separated string cannot express, so only `non_empty` is
+/// kept for lists.
+fn add_constraints(schema: &mut Map<String, Value>, kind: &FieldKind, constraints: &[Constraint]) {
+    if let FieldKind::List { .. } = kind {
+        if constraints.contains(&Constraint::NonEmpty) {
+            schema.insert("minLength".to_string(), json!(1));
+        }
+        return;
+    }
+
+    for constraint in constraints {
+        match constraint {
+            Constraint::Range { min, max } => {
+                insert_bound(schema, "minimum", min);
+                insert_bound(schema, "maximum", max);
+            }
+            Constraint::Length { min, max } => {
+                insert_bound(schema, "minLength", min);
+                insert_bound(schema, "maxLength", max);
+            }
+            Constraint::NonEmpty => {
+                schema.insert("minLength".to_string(), json!(1));
+            }
+            Constraint::Regex(pattern) => {
+                schema.insert("pattern".to_string(), json!(pattern));
+            }
+            Constraint::OneOf(allowed) => {
+                schema.insert("enum".to_string(), json!(allowed));
+            }
+            Constraint::With(..) => {}
+        }
+    }
+}
+
+/// Adds a bound written as a number literal. Bounds written as other expressions, such as a
+/// constant, cannot be evaluated here and are left out.
+fn insert_bound(schema: &mut Map<String, Value>, key: &str, bound: &Option<&str>) {
+    let bound: String = match bound {
+        Some(bound) => bound
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '_')
+            .collect(),
+        None => return,
+    };
+    if let Ok(bound) = bound.parse::<i64>() {
+        schema.insert(key.to_string(), json!(bound));
+    } else if let Ok(bound) = bound.parse::<f64>() {
+        schema.insert(key.to_string(), json!(bound));
+    }
+}
+
+fn default_value(kind: &FieldKind, default: &str) -> Value {
+    let parsed = match kind {
+        FieldKind::Integer | FieldKind::Bytes | FieldKind::Duration { .. } => {
+            default.trim().parse::<i64>().ok().map(Value::from)
+        }
+        FieldKind::Float => default.trim().parse::<f64>().ok().map(Value::from),
+        FieldKind::Bool => crate::parse::boolean(default).ok().map(Value::from),
+        _ => None,
+    };
+    parsed.unwrap_or_else(|| json!(default))
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+use std::str::FromStr;
+
+use zeroize::Zeroize;
+
+/// A value that is kept out of logs and wiped from memory when it is dropped.
+///
+/// `Secret<T>` can be used as the type of a `ConfigStruct` field holding a `String`, `bool`, a
+/// number or a `Vec` of these, which are the types the derive loads that implement `Zeroize`.
+/// Its `Debug` output never includes the value, so a struct containing it can derive `Debug` and
+/// be logged safely, and the field is redacted in `describe()` reports and parse errors as if it
+/// was marked with `#[secret]`. The value can only be read with
+/// [`expose_secret`](Secret::expose_secret).
+///
+/// ```rust
+/// use env_extract::{ConfigStruct, Secret};
+///
+/// #[derive(ConfigStruct, Debug)]
+/// struct Config {
+///     secret_example_password: Secret<String>,
+/// }
+///
+/// fn main() {
+///     std::env::set_var("SECRET_EXAMPLE_PASSWORD", "hunter2");
+///
+///     let config = Config::get();
+///     assert_eq!(config.secret_example_password.expose_secret(), "hunter2");
+///     assert!(!format!("{:?}", config).contains("hunter2"));
+/// }
+/// ```
+pub struct Secret<T: Zeroize>(T);
+
+impl<T: Zeroize> Secret<T> {
+    /// Wraps `value`.
+    pub fn new(value: T) -> Self {
+        Secret(value)
+    }
+
+    /// Returns a reference to the secret value.
+    pub fn expose_secret(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Zeroize> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Secret(value)
+    }
+}
+
+impl<T: Zeroize + Clone> Clone for Secret<T> {
+    fn clone(&self) -> Self {
+        Secret(self.0.clone())
+    }
+}
+
+impl<T: Zeroize + Default> Default for Secret<T> {
+    fn default() -> Self {
+        Secret(T::default())
+    }
+}
+
+impl<T: Zeroize + FromStr> FromStr for Secret<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Secret)
+    }
+}
+
+impl<T: Zeroize> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(<redacted>)")
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
@@ -0,0 +1,118 @@
+//! Places that the derived loaders can read values from.
+//!
+//! `get()` and `try_get()` read from the process environment, but every derived type can also be
+//! loaded from any [`Source`] with `get_from` and `try_get_from`:
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use env_extract::{ConfigStruct, Env, Source};
+//!
+//! #[derive(ConfigStruct)]
+//! struct Config {
+//!     source_example_host: String,
+//!     source_example_port: u16,
+//! }
+//!
+//! fn main() {
+//!     let mut overrides = HashMap::new();
+//!     overrides.insert("SOURCE_EXAMPLE_HOST", "localhost");
+//!     overrides.insert("SOURCE_EXAMPLE_PORT", "8080");
+//!
+//!     // Values set in the process environment win over the map.
+//!     let config = Config::get_from(&Env.or(overrides));
+//!     assert_eq!(config.source_example_host, "localhost");
+//! }
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+use crate::dotenv::Dotenv;
+use crate::file::ConfigFile;
+
+/// A set of named string values that configuration can be read from.
+pub trait Source {
+    /// Returns the value stored under `key`, if there is one.
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Returns a source that reads from `self` first, and from `fallback` for keys that `self`
+    /// does not have.
+    fn or<S: Source>(self, fallback: S) -> Chain<Self, S>
+    where
+        Self: Sized,
+    {
+        Chain {
+            first: self,
+            second: fallback,
+        }
+    }
+}
+
+/// The process environment.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Env;
+
+impl Source for Env {
+    fn get(&self, key: &str) -> Option<String> {
+        std::env::var(key).ok()
+    }
+}
+
+/// Two sources layered on top of each other, created with [`Source::or`].
+#[derive(Debug, Clone)]
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: Source, B: Source> Source for Chain<A, B> {
+    fn get(&self, key: &str) -> Option<String> {
+        self.first.get(key).or_else(|| self.second.get(key))
+    }
+}
+
+impl<K, V, H> Source for HashMap<K, V, H>
+where
+    K: Borrow<str> + Hash + Eq,
+    V: AsRef<str>,
+    H: BuildHasher,
+{
+    fn get(&self, key: &str) -> Option<String> {
+        HashMap::get(self, key).map(|v| v.as_ref().to_string())
+    }
+}
+
+impl<K, V> Source for BTreeMap<K, V>
+where
+    K: Borrow<str> + Ord,
+    V: AsRef<str>,
+{
+    fn get(&self, key: &str) -> Option<String> {
+        BTreeMap::get(self, key).map(|v| v.as_ref().to_string())
+    }
+}
+
+impl Source for Dotenv {
+    fn get(&self, key: &str) -> Option<String> {
+        Dotenv::get(self, key).map(str::to_string)
+    }
+}
+
+impl Source for ConfigFile {
+    fn get(&self, key: &str) -> Option<String> {
+        ConfigFile::get(self, key).map(str::to_string)
+    }
+}
+
+impl<S: Source + ?Sized> Source for &S {
+    fn get(&self, key: &str) -> Option<String> {
+        (**self).get(key)
+    }
+}
+
+impl<S: Source + ?Sized> Source for Box<S> {
+    fn get(&self, key: &str) -> Option<String> {
+        (**self).get(key)
+    }
+}
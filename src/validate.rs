@@ -0,0 +1,136 @@
+//! Checks used by the `#[validate(...)]` attribute of `#[derive(ConfigStruct)]`.
+//!
+//! Each check returns `Err` with a message describing the value that would have been accepted.
+//! The derived loader reports the message as a [`FieldError::Invalid`](crate::FieldError::Invalid)
+//! for the field, alongside the value that was read.
+
+use std::fmt::Debug;
+
+/// Checks that `value` lies between `min` and `max`, inclusive. Either bound may be left out.
+///
+/// ```rust
+/// use env_extract::validate::range;
+///
+/// assert!(range(&8080, Some(1), Some(65535)).is_ok());
+/// assert_eq!(range(&0, Some(1), Some(65535)).unwrap_err(), "must be between 1 and 65535");
+/// assert_eq!(range(&0.5, Some(1.0), None).unwrap_err(), "must be at least 1.0");
+/// ```
+pub fn range<T: PartialOrd + Debug>(
+    value: &T,
+    min: Option<T>,
+    max: Option<T>,
+) -> Result<(), String> {
+    let too_small = min.as_ref().is_some_and(|min| value < min);
+    let too_large = max.as_ref().is_some_and(|max| value > max);
+    if !too_small && !too_large {
+        return Ok(());
+    }
+
+    match (min, max) {
+        (Some(min), Some(max)) => Err(format!("must be between {:?} and {:?}", min, max)),
+        (Some(min), None) => Err(format!("must be at least {:?}", min)),
+        (None, Some(max)) => Err(format!("must be at most {:?}", max)),
+        (None, None) => Ok(()),
+    }
+}
+
+/// Checks that a length, such as the number of characters in a string or items in a list, lies
+/// between `min` and `max`, inclusive.
+///
+/// ```rust
+/// use env_extract::validate::length;
+///
+/// assert!(length(3, Some(1), Some(8)).is_ok());
+/// assert_eq!(length(12, None, Some(8)).unwrap_err(), "must have a length of at most 8, found 12");
+/// ```
+pub fn length(len: usize, min: Option<usize>, max: Option<usize>) -> Result<(), String> {
+    let too_small = min.is_some_and(|min| len < min);
+    let too_large = max.is_some_and(|max| len > max);
+    if !too_small && !too_large {
+        return Ok(());
+    }
+
+    match (min, max) {
+        (Some(min), Some(max)) if min == max => {
+            Err(format!("must have a length of {}, found {}", min, len))
+        }
+        (Some(min), Some(max)) => Err(format!(
+            "must have a length between {} and {}, found {}",
+            min, max, len
+        )),
+        (Some(min), None) => Err(format!(
+            "must have a length of at least {}, found {}",
+            min, len
+        )),
+        (None, Some(max)) => Err(format!(
+            "must have a length of at most {}, found {}",
+            max, len
+        )),
+        (None, None) => Ok(()),
+    }
+}
+
+/// Checks that a string or list is not empty, given its length.
+///
+/// ```rust
+/// use env_extract::validate::non_empty;
+///
+/// assert!(non_empty("admin".len()).is_ok());
+/// assert_eq!(non_empty(0).unwrap_err(), "must not be empty");
+/// ```
+pub fn non_empty(len: usize) -> Result<(), String> {
+    if len == 0 {
+        Err("must not be empty".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that `value` is one of the `allowed` strings.
+///
+/// ```rust
+/// use env_extract::validate::one_of;
+///
+/// assert!(one_of("debug", &["debug", "info"]).is_ok());
+/// assert_eq!(one_of("trace", &["debug", "info"]).unwrap_err(), "must be one of debug, info");
+/// ```
+pub fn one_of(value: &str, allowed: &[&str]) -> Result<(), String> {
+    if allowed.contains(&value) {
+        Ok(())
+    } else {
+        Err(format!("must be one of {}", allowed.join(", ")))
+    }
+}
+
+/// Checks that `value` matches the regular expression `pattern`. The pattern is not anchored, so
+/// use `^` and `$` to match the whole value.
+///
+/// This requires the `regex` feature, which is enabled by default.
+///
+/// ```rust
+/// use env_extract::validate::regex;
+///
+/// assert!(regex("eu-west-1", "^[a-z]+-[a-z]+-[0-9]$").is_ok());
+/// assert_eq!(
+///     regex("EU", "^[a-z]+$").unwrap_err(),
+///     "must match the pattern \"^[a-z]+$\""
+/// );
+/// ```
+#[cfg(feature = "regex")]
+pub fn regex(value: &str, pattern: &str) -> Result<(), String> {
+    is_match(value, pattern, &compile(pattern)?)
+}
+
+#[cfg(feature = "regex")]
+pub(crate) fn compile(pattern: &str) -> Result<regex::Regex, String> {
+    regex::Regex::new(pattern).map_err(|e| format!("invalid pattern {:?}: {}", pattern, e))
+}
+
+#[cfg(feature = "regex")]
+pub(crate) fn is_match(value: &str, pattern: &str, regex: &regex::Regex) -> Result<(), String> {
+    if regex.is_match(value) {
+        Ok(())
+    } else {
+        Err(format!("must match the pattern {:?}", pattern))
+    }
+}
@@ -0,0 +1,124 @@
+use env_extract::dotenv::{Dotenv, DotenvError};
+use env_extract::ConfigStruct;
+
+#[test]
+fn test_parse_basic_values() {
+    let dotenv = Dotenv::parse(
+        "# a comment\n\
+         HOST=localhost\n\
+         export PORT = 8080 # inline comment\n\
+         EMPTY=\n\
+         HASH=a#b\n",
+    )
+    .unwrap();
+
+    assert_eq!(dotenv.get("HOST"), Some("localhost"));
+    assert_eq!(dotenv.get("PORT"), Some("8080"));
+    assert_eq!(dotenv.get("EMPTY"), Some(""));
+    assert_eq!(dotenv.get("HASH"), Some("a#b"));
+    assert_eq!(dotenv.get("MISSING"), None);
+}
+
+#[test]
+fn test_parse_quoted_values() {
+    let dotenv = Dotenv::parse(
+        r#"DOUBLE="Hello,\n\"World\" \$HOME"
+SINGLE='raw \n $HOME # not a comment'
+MULTILINE="first
+second"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(dotenv.get("DOUBLE"), Some("Hello,\n\"World\" $HOME"));
+    assert_eq!(dotenv.get("SINGLE"), Some("raw \\n $HOME # not a comment"));
+    assert_eq!(dotenv.get("MULTILINE"), Some("first\nsecond"));
+}
+
+#[test]
+fn test_parse_interpolation() {
+    std::env::set_var("DOTENV_TEST_PROCESS_VAR", "from-process");
+    std::env::remove_var("DOTENV_TEST_UNSET");
+
+    let dotenv = Dotenv::parse(
+        "HOST=db.local\n\
+         PORT=5432\n\
+         URL=postgres://${HOST}:$PORT/app\n\
+         PROCESS=\"${DOTENV_TEST_PROCESS_VAR}\"\n\
+         FALLBACK=${DOTENV_TEST_UNSET:-default}\n\
+         UNKNOWN=[${DOTENV_TEST_UNSET}]\n",
+    )
+    .unwrap();
+
+    assert_eq!(dotenv.get("URL"), Some("postgres://db.local:5432/app"));
+    assert_eq!(dotenv.get("PROCESS"), Some("from-process"));
+    assert_eq!(dotenv.get("FALLBACK"), Some("default"));
+    assert_eq!(dotenv.get("UNKNOWN"), Some("[]"));
+}
+
+#[test]
+fn test_bare_interpolation_stops_at_dot() {
+    std::env::remove_var("HOST");
+
+    let dotenv = Dotenv::parse("HOST=db\nURL=http://$HOST.example.com/").unwrap();
+
+    assert_eq!(dotenv.get("URL"), Some("http://db.example.com/"));
+}
+
+#[test]
+fn test_interpolation_prefers_process_env() {
+    std::env::set_var("DOTENV_TEST_OVERRIDDEN", "env");
+
+    let dotenv = Dotenv::parse(
+        "DOTENV_TEST_OVERRIDDEN=file\n\
+         DOTENV_TEST_DERIVED=${DOTENV_TEST_OVERRIDDEN}\n",
+    )
+    .unwrap();
+
+    assert_eq!(dotenv.get("DOTENV_TEST_OVERRIDDEN"), Some("file"));
+    assert_eq!(dotenv.get("DOTENV_TEST_DERIVED"), Some("env"));
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(matches!(
+        Dotenv::parse("HOST=localhost\nNOT AN ASSIGNMENT\n"),
+        Err(DotenvError::Syntax { line: 2, .. })
+    ));
+    assert!(matches!(
+        Dotenv::parse("A=1\nB=\"unterminated\n"),
+        Err(DotenvError::Syntax { line: 2, .. })
+    ));
+    assert!(matches!(
+        Dotenv::parse("A='value' trailing\n"),
+        Err(DotenvError::Syntax { line: 1, .. })
+    ));
+}
+
+#[derive(Debug, ConfigStruct)]
+struct DotenvConfig {
+    dotenv_file_host: String,
+    dotenv_file_port: u16,
+}
+
+#[test]
+fn test_from_dotenv() {
+    let path = std::env::temp_dir().join(format!("env-extract-{}.env", std::process::id()));
+    std::fs::write(&path, "DOTENV_FILE_HOST=file.local\nDOTENV_FILE_PORT=80\n").unwrap();
+    std::env::remove_var("DOTENV_FILE_HOST");
+    std::env::set_var("DOTENV_FILE_PORT", "8080");
+
+    let config = DotenvConfig::from_dotenv(&path);
+    std::fs::remove_file(&path).unwrap();
+    let config = config.unwrap();
+
+    assert_eq!(config.dotenv_file_host, "file.local");
+    assert_eq!(config.dotenv_file_port, 8080);
+    assert!(std::env::var("DOTENV_FILE_HOST").is_err());
+}
+
+#[test]
+fn test_from_dotenv_missing_file() {
+    let error = DotenvConfig::from_dotenv("/nonexistent/.env").unwrap_err();
+    assert!(matches!(error, DotenvError::Io { .. }));
+}
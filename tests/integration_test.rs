@@ -1,7 +1,11 @@
+#![allow(clippy::bool_assert_comparison)]
+
 #[cfg(test)]
 mod tests {
-    use env_extract::{ConfigStruct, EnvVar};
+    use env_extract::{ConfigStruct, Env, EnvVar, Origin, Secret, Source};
+    use std::collections::{BTreeMap, HashMap};
     use std::env;
+    use std::time::Duration;
 
     #[derive(EnvVar)]
     enum LogLevel {
@@ -278,4 +282,1932 @@ mod tests {
         assert_eq!(config.int_field, 42);
         assert!(matches!(config.enum_field, StructTestEnum::VariantA));
     }
+
+    #[derive(Debug, EnvVar)]
+    #[var_name = "TRY_GET_ENUM"]
+    enum TryGetEnum {
+        VariantA,
+        Invalid,
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct TryGetConfig {
+        try_get_string: String,
+        try_get_number: u16,
+        #[default("7")]
+        try_get_defaulted: u8,
+
+        #[enumerated]
+        try_get_enum: TryGetEnum,
+    }
+
+    #[test]
+    fn test_try_get_success() {
+        std::env::set_var("TRY_GET_STRING", "hello");
+        std::env::set_var("TRY_GET_NUMBER", "8080");
+        std::env::set_var("TRY_GET_ENUM", "VariantA");
+
+        let config = TryGetConfig::try_get().unwrap();
+
+        assert_eq!(config.try_get_string, "hello");
+        assert_eq!(config.try_get_number, 8080);
+        assert_eq!(config.try_get_defaulted, 7);
+        assert!(matches!(config.try_get_enum, TryGetEnum::VariantA));
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    struct TryGetFailureConfig {
+        try_get_failure_missing: String,
+        try_get_failure_number: u16,
+        #[default("not a number")]
+        try_get_failure_bad_default: i32,
+    }
+
+    #[test]
+    fn test_try_get_reports_every_field() {
+        std::env::remove_var("TRY_GET_FAILURE_MISSING");
+        std::env::set_var("TRY_GET_FAILURE_NUMBER", "99999");
+        std::env::remove_var("TRY_GET_FAILURE_BAD_DEFAULT");
+
+        let errors = TryGetFailureConfig::try_get().unwrap_err().into_errors();
+
+        assert_eq!(errors.len(), 3);
+        assert_eq!(
+            errors[0],
+            env_extract::FieldError::Missing {
+                field: "try_get_failure_missing".to_string(),
+                var_name: "TRY_GET_FAILURE_MISSING".to_string(),
+            }
+        );
+        assert!(matches!(
+            &errors[1],
+            env_extract::FieldError::Invalid { value, .. } if value == "99999"
+        ));
+        assert_eq!(errors[2].field(), "try_get_failure_bad_default");
+    }
+
+    #[derive(Debug, EnvVar)]
+    #[var_name = "GET_RESULT_ENUM"]
+    #[case(convert = "uppercase")]
+    #[panic_on_invalid]
+    enum GetResultEnum {
+        #[case(convert = "lowercase")]
+        Alpha,
+        Beta,
+    }
+
+    #[test]
+    fn test_get_result_errors() {
+        std::env::remove_var("GET_RESULT_ENUM");
+        assert_eq!(
+            GetResultEnum::get_result().unwrap_err(),
+            env_extract::EnvVarError::Missing {
+                var_name: "GET_RESULT_ENUM".to_string()
+            }
+        );
+
+        std::env::set_var("GET_RESULT_ENUM", "gamma");
+        assert_eq!(
+            GetResultEnum::get_result().unwrap_err(),
+            env_extract::EnvVarError::Invalid {
+                var_name: "GET_RESULT_ENUM".to_string(),
+                value: "gamma".to_string(),
+                expected: &["alpha", "BETA"],
+            }
+        );
+
+        std::env::set_var("GET_RESULT_ENUM", "BETA");
+        assert!(matches!(
+            GetResultEnum::get_result(),
+            Ok(GetResultEnum::Beta)
+        ));
+    }
+
+    fn load_config<T: ConfigStruct>() -> Result<T, env_extract::ConfigError> {
+        T::try_get()
+    }
+
+    fn load_enum<T: EnvVar>() -> Result<T, env_extract::EnvVarError> {
+        T::get_result()
+    }
+
+    #[derive(Debug, EnvVar)]
+    #[var_name = "GENERIC_ENUM"]
+    enum GenericEnum {
+        VariantA,
+        Invalid,
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct GenericConfig {
+        generic_number: u32,
+    }
+
+    #[test]
+    fn test_traits_as_generic_bounds() {
+        std::env::set_var("GENERIC_NUMBER", "12");
+        std::env::set_var("GENERIC_ENUM", "VariantA");
+
+        let config: GenericConfig = load_config().unwrap();
+        assert_eq!(config.generic_number, 12);
+
+        let value: GenericEnum = load_enum().unwrap();
+        assert!(matches!(value, GenericEnum::VariantA));
+    }
+
+    #[derive(Debug, Default, PartialEq, EnvVar)]
+    #[var_name = "DEFAULT_DERIVE_ENUM"]
+    enum DefaultDeriveEnum {
+        Fast,
+        #[default]
+        Safe,
+    }
+
+    #[test]
+    fn test_enum_deriving_default() {
+        assert_eq!(DefaultDeriveEnum::default(), DefaultDeriveEnum::Safe);
+        assert_eq!(DefaultDeriveEnum::fallback(), DefaultDeriveEnum::Safe);
+
+        let source = HashMap::from([("DEFAULT_DERIVE_ENUM", "Fast")]);
+        assert_eq!(
+            DefaultDeriveEnum::get_from(&source),
+            DefaultDeriveEnum::Fast
+        );
+    }
+
+    #[derive(Debug, PartialEq, EnvVar)]
+    #[var_name = "OPTIONAL_ENUM"]
+    #[panic_on_invalid]
+    enum OptionalEnum {
+        VariantA,
+        VariantB,
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct OptionalConfig {
+        optional_string: Option<String>,
+        optional_number: Option<u16>,
+        #[default("5")]
+        optional_defaulted: Option<u8>,
+
+        #[enumerated]
+        optional_enum: Option<OptionalEnum>,
+    }
+
+    #[test]
+    fn test_optional_fields_absent() {
+        std::env::remove_var("OPTIONAL_STRING");
+        std::env::remove_var("OPTIONAL_NUMBER");
+        std::env::remove_var("OPTIONAL_DEFAULTED");
+        std::env::remove_var("OPTIONAL_ENUM");
+
+        let config = OptionalConfig::try_get().unwrap();
+
+        assert_eq!(config.optional_string, None);
+        assert_eq!(config.optional_number, None);
+        assert_eq!(config.optional_defaulted, Some(5));
+        assert_eq!(config.optional_enum, None);
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct OptionalPresentConfig {
+        optional_present_string: Option<String>,
+        optional_present_number: Option<u16>,
+    }
+
+    #[test]
+    fn test_optional_fields_present() {
+        std::env::set_var("OPTIONAL_PRESENT_STRING", "value");
+        std::env::set_var("OPTIONAL_PRESENT_NUMBER", "443");
+
+        let config = OptionalPresentConfig::try_get().unwrap();
+
+        assert_eq!(config.optional_present_string.as_deref(), Some("value"));
+        assert_eq!(config.optional_present_number, Some(443));
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    struct OptionalInvalidConfig {
+        optional_invalid_number: Option<u16>,
+    }
+
+    #[test]
+    fn test_optional_field_invalid_value() {
+        std::env::set_var("OPTIONAL_INVALID_NUMBER", "-1");
+
+        let errors = OptionalInvalidConfig::try_get().unwrap_err().into_errors();
+
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            env_extract::FieldError::Invalid { value, .. } if value == "-1"
+        ));
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct ListConfig {
+        list_origins: Vec<String>,
+        list_ports: std::collections::HashSet<u16>,
+        #[list(separator = ";", trim = false, skip_empty = false)]
+        list_raw: Vec<String>,
+        #[default("")]
+        list_defaulted: std::collections::BTreeSet<i32>,
+        list_optional: Option<Vec<u8>>,
+    }
+
+    #[test]
+    fn test_list_fields() {
+        std::env::set_var("LIST_ORIGINS", "a.example, b.example,,c.example ");
+        std::env::set_var("LIST_PORTS", "80,443,80");
+        std::env::set_var("LIST_RAW", " a;;b");
+        std::env::remove_var("LIST_DEFAULTED");
+        std::env::remove_var("LIST_OPTIONAL");
+
+        let config = ListConfig::try_get().unwrap();
+
+        assert_eq!(config.list_origins, ["a.example", "b.example", "c.example"]);
+        assert_eq!(config.list_ports, [80, 443].into_iter().collect());
+        assert_eq!(config.list_raw, [" a", "", "b"]);
+        assert!(config.list_defaulted.is_empty());
+        assert_eq!(config.list_optional, None);
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    struct InvalidListConfig {
+        invalid_list_ports: Vec<u16>,
+    }
+
+    #[test]
+    fn test_list_field_invalid_item() {
+        std::env::set_var("INVALID_LIST_PORTS", "80,http,443");
+
+        let errors = InvalidListConfig::try_get().unwrap_err().into_errors();
+
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            env_extract::FieldError::Invalid { message, .. } if message.starts_with("invalid item \"http\"")
+        ));
+    }
+
+    #[derive(Debug, PartialEq, EnvVar)]
+    #[case(convert = "lowercase")]
+    enum ListLevel {
+        Error,
+        Warning,
+        Info,
+        Invalid,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    struct EnumListConfig {
+        #[enumerated]
+        list_levels: Vec<ListLevel>,
+        #[enumerated]
+        #[list(separator = "|")]
+        list_optional_levels: Option<Vec<ListLevel>>,
+    }
+
+    #[test]
+    fn test_enum_list_fields() {
+        let source = HashMap::from([("LIST_LEVELS", "error, info")]);
+        let config = EnumListConfig::get_from(&source);
+        assert_eq!(config.list_levels, [ListLevel::Error, ListLevel::Info]);
+        assert_eq!(config.list_optional_levels, None);
+
+        let source = HashMap::from([
+            ("LIST_LEVELS", "warning"),
+            ("LIST_OPTIONAL_LEVELS", "info|debug"),
+        ]);
+        let errors = EnumListConfig::try_get_from(&source)
+            .unwrap_err()
+            .into_errors();
+        assert_eq!(
+            errors,
+            [env_extract::FieldError::Invalid {
+                field: "list_optional_levels".to_string(),
+                var_name: "LIST_OPTIONAL_LEVELS".to_string(),
+                value: "info|debug".to_string(),
+                message: "invalid item \"debug\": expected one of error, warning, info".to_string(),
+            }]
+        );
+
+        assert!(EnumListConfig::env_example()
+            .contains("# Each item one of: error, warning, info\nLIST_LEVELS=\n"));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Percentage(u8);
+
+    impl std::str::FromStr for Percentage {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s.strip_suffix('%').and_then(|v| v.parse().ok()) {
+                Some(v) if v <= 100 => Ok(Percentage(v)),
+                _ => Err(format!("{} is not a percentage", s)),
+            }
+        }
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct FromStrConfig {
+        #[parse]
+        from_str_path: std::path::PathBuf,
+        #[parse]
+        from_str_addresses: Vec<std::net::IpAddr>,
+        #[parse]
+        from_str_socket: Option<std::net::SocketAddr>,
+        #[parse]
+        #[default("50%")]
+        from_str_percentage: Percentage,
+    }
+
+    #[test]
+    fn test_from_str_fields() {
+        std::env::set_var("FROM_STR_PATH", "/etc/app.toml");
+        std::env::set_var("FROM_STR_ADDRESSES", "127.0.0.1, ::1");
+        std::env::set_var("FROM_STR_SOCKET", "0.0.0.0:8080");
+        std::env::remove_var("FROM_STR_PERCENTAGE");
+
+        let config = FromStrConfig::try_get().unwrap();
+
+        assert_eq!(config.from_str_path, std::path::Path::new("/etc/app.toml"));
+        assert_eq!(config.from_str_addresses.len(), 2);
+        assert_eq!(config.from_str_socket.unwrap().port(), 8080);
+        assert_eq!(config.from_str_percentage, Percentage(50));
+
+        std::env::set_var("FROM_STR_PERCENTAGE", "150%");
+        let errors = FromStrConfig::try_get().unwrap_err().into_errors();
+        std::env::remove_var("FROM_STR_PERCENTAGE");
+
+        assert_eq!(
+            errors,
+            [env_extract::FieldError::Invalid {
+                field: "from_str_percentage".to_string(),
+                var_name: "FROM_STR_PERCENTAGE".to_string(),
+                value: "150%".to_string(),
+                message: "150% is not a percentage".to_string(),
+            }]
+        );
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct NestedDbConfig {
+        host: String,
+        #[default(5432)]
+        port: u16,
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct NestedAppConfig {
+        #[nested]
+        nested_db: NestedDbConfig,
+        #[nested(prefix = "NESTED_REPLICA", separator = "__")]
+        replica: NestedDbConfig,
+    }
+
+    #[test]
+    fn test_nested_fields() {
+        std::env::set_var("NESTED_DB_HOST", "primary.local");
+        std::env::set_var("NESTED_DB_PORT", "6543");
+        std::env::set_var("NESTED_REPLICA__HOST", "replica.local");
+        std::env::remove_var("NESTED_REPLICA__PORT");
+
+        let config = NestedAppConfig::try_get().unwrap();
+
+        assert_eq!(config.nested_db.host, "primary.local");
+        assert_eq!(config.nested_db.port, 6543);
+        assert_eq!(config.replica.host, "replica.local");
+        assert_eq!(config.replica.port, 5432);
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    struct NestedOuterConfig {
+        #[nested]
+        inner: NestedDbConfig,
+    }
+
+    #[test]
+    fn test_nested_fields_with_prefix() {
+        std::env::set_var("NESTED_OUTER_INNER_HOST", "db.local");
+        std::env::set_var("NESTED_OUTER_INNER_PORT", "not a port");
+
+        let errors = NestedOuterConfig::try_get_with_prefix("NESTED_OUTER_")
+            .unwrap_err()
+            .into_errors();
+
+        assert_eq!(
+            errors,
+            [env_extract::FieldError::Invalid {
+                field: "inner.port".to_string(),
+                var_name: "NESTED_OUTER_INNER_PORT".to_string(),
+                value: "not a port".to_string(),
+                message: "invalid digit found in string".to_string(),
+            }]
+        );
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct NestedPoolConfig {
+        #[enumerated]
+        mode: AliasMode,
+        #[enumerated]
+        strict: OptionalEnum,
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct NestedPoolsConfig {
+        #[nested(prefix = "PRIMARY")]
+        primary: NestedPoolConfig,
+        #[nested(prefix = "REPLICA")]
+        replica: NestedPoolConfig,
+    }
+
+    #[test]
+    fn test_nested_enumerated_fields() {
+        let source = HashMap::from([
+            ("PRIMARY_ALIAS_MODE", "fast"),
+            ("PRIMARY_OPTIONAL_ENUM", "VariantA"),
+            ("REPLICA_ALIAS_LEGACY_MODE", "safe"),
+            ("REPLICA_OPTIONAL_ENUM", "VariantB"),
+        ]);
+        let config = NestedPoolsConfig::try_get_from(&source).unwrap();
+        assert_eq!(config.primary.mode, AliasMode::Fast);
+        assert_eq!(config.primary.strict, OptionalEnum::VariantA);
+        assert_eq!(config.replica.mode, AliasMode::Safe);
+        assert_eq!(config.replica.strict, OptionalEnum::VariantB);
+
+        let source = HashMap::from([("PRIMARY_OPTIONAL_ENUM", "VariantA")]);
+        let errors = NestedPoolsConfig::try_get_from(&source)
+            .unwrap_err()
+            .into_errors();
+        assert_eq!(
+            errors,
+            [env_extract::FieldError::Missing {
+                field: "replica.strict".to_string(),
+                var_name: "REPLICA_OPTIONAL_ENUM".to_string(),
+            }]
+        );
+
+        assert_eq!(
+            NestedPoolsConfig::env_example(),
+            "\
+# One of: fast, safe
+# Also read from: PRIMARY_ALIAS_LEGACY_MODE
+# PRIMARY_ALIAS_MODE=
+
+# One of: VariantA, VariantB
+PRIMARY_OPTIONAL_ENUM=
+
+# One of: fast, safe
+# Also read from: REPLICA_ALIAS_LEGACY_MODE
+# REPLICA_ALIAS_MODE=
+
+# One of: VariantA, VariantB
+REPLICA_OPTIONAL_ENUM=
+"
+        );
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    #[env_prefix = "BILLING_"]
+    struct PrefixedConfig {
+        db_host: String,
+        #[var_name = "PREFIXED_UNPREFIXED"]
+        unprefixed: String,
+    }
+
+    #[test]
+    fn test_env_prefix() {
+        std::env::set_var("BILLING_DB_HOST", "billing.local");
+        std::env::set_var("INVOICING_DB_HOST", "invoicing.local");
+        std::env::set_var("PREFIXED_UNPREFIXED", "shared");
+
+        let config = PrefixedConfig::get();
+        assert_eq!(config.db_host, "billing.local");
+        assert_eq!(config.unprefixed, "shared");
+
+        let config = PrefixedConfig::get_with_prefix("INVOICING_");
+        assert_eq!(config.db_host, "invoicing.local");
+        assert_eq!(config.unprefixed, "shared");
+    }
+
+    #[derive(Debug, PartialEq, EnvVar)]
+    #[var_name = "SOURCE_MODE"]
+    #[case(convert = "lowercase")]
+    enum SourceMode {
+        Fast,
+        Safe,
+        Invalid,
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct SourceConfig {
+        source_host: String,
+        #[default(80)]
+        source_port: u16,
+        #[enumerated]
+        mode: SourceMode,
+        #[nested(prefix = "SOURCE_DB")]
+        db: NestedDbConfig,
+    }
+
+    #[test]
+    fn test_get_from_map_source() {
+        std::env::remove_var("SOURCE_HOST");
+
+        let source = HashMap::from([
+            ("SOURCE_HOST", "map.local"),
+            ("SOURCE_MODE", "safe"),
+            ("SOURCE_DB_HOST", "db.local"),
+        ]);
+        let config = SourceConfig::get_from(&source);
+
+        assert_eq!(config.source_host, "map.local");
+        assert_eq!(config.source_port, 80);
+        assert_eq!(config.mode, SourceMode::Safe);
+        assert_eq!(config.db.host, "db.local");
+        assert!(SourceConfig::try_get().is_err());
+    }
+
+    #[test]
+    fn test_try_get_from_map_source_errors() {
+        let source = BTreeMap::from([("SOURCE_MODE".to_string(), "slow".to_string())]);
+        let errors = SourceConfig::try_get_from(&source)
+            .unwrap_err()
+            .into_errors();
+
+        let fields: Vec<&str> = errors.iter().map(|e| e.field()).collect();
+        assert_eq!(fields, ["source_host", "db.host"]);
+
+        let source = BTreeMap::from([
+            ("SOURCE_HOST".to_string(), "localhost".to_string()),
+            ("SOURCE_DB_HOST".to_string(), "db.local".to_string()),
+            ("SOURCE_MODE".to_string(), "slow".to_string()),
+        ]);
+        let config = SourceConfig::try_get_from(&source).unwrap();
+        assert_eq!(config.mode, SourceMode::Invalid);
+
+        assert_eq!(
+            SourceMode::try_get_from(&source),
+            Err(env_extract::EnvVarError::Invalid {
+                var_name: "SOURCE_MODE".to_string(),
+                value: "slow".to_string(),
+                expected: &["fast", "safe", "invalid"],
+            })
+        );
+        assert_eq!(SourceMode::get_from(&source), SourceMode::Invalid);
+    }
+
+    #[derive(Debug, PartialEq, EnvVar)]
+    #[var_name = "FALLBACK_SPEED"]
+    enum FallbackSpeed {
+        Fast,
+        #[default]
+        Slow,
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct FallbackConfig {
+        #[enumerated]
+        mode: SourceMode,
+        #[enumerated]
+        speed: FallbackSpeed,
+        #[enumerated]
+        optional_speed: Option<FallbackSpeed>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    struct PanickingEnumConfig {
+        #[enumerated]
+        optional_enum: OptionalEnum,
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct DefaultedEnumConfig {
+        #[enumerated]
+        #[default("VariantB")]
+        optional_enum: OptionalEnum,
+    }
+
+    #[test]
+    fn test_enumerated_fallbacks() {
+        let config = FallbackConfig::try_get_from(&HashMap::<&str, &str>::new()).unwrap();
+        assert_eq!(config.mode, SourceMode::Invalid);
+        assert_eq!(config.speed, FallbackSpeed::Slow);
+        assert_eq!(config.optional_speed, None);
+
+        let source = HashMap::from([("SOURCE_MODE", "slow"), ("FALLBACK_SPEED", "warp")]);
+        let config = FallbackConfig::try_get_from(&source).unwrap();
+        assert_eq!(config.mode, SourceMode::Invalid);
+        assert_eq!(config.speed, FallbackSpeed::Slow);
+        assert_eq!(config.optional_speed, Some(FallbackSpeed::Slow));
+
+        assert!(!FallbackConfig::FIELDS[0].required());
+        assert!(!FallbackConfig::FIELDS[1].required());
+
+        let errors = PanickingEnumConfig::try_get_from(&HashMap::from([("OPTIONAL_ENUM", "c")]))
+            .unwrap_err()
+            .into_errors();
+        assert_eq!(
+            errors,
+            [env_extract::FieldError::Enum {
+                field: "optional_enum".to_string(),
+                source: env_extract::EnvVarError::Invalid {
+                    var_name: "OPTIONAL_ENUM".to_string(),
+                    value: "c".to_string(),
+                    expected: &["VariantA", "VariantB"],
+                },
+            }]
+        );
+        assert!(PanickingEnumConfig::FIELDS[0].required());
+    }
+
+    #[test]
+    fn test_enumerated_default() {
+        let config = DefaultedEnumConfig::try_get_from(&HashMap::<&str, &str>::new()).unwrap();
+        assert_eq!(config.optional_enum, OptionalEnum::VariantB);
+        assert!(!DefaultedEnumConfig::FIELDS[0].required());
+
+        let source = HashMap::from([("OPTIONAL_ENUM", "VariantA")]);
+        let config = DefaultedEnumConfig::try_get_from(&source).unwrap();
+        assert_eq!(config.optional_enum, OptionalEnum::VariantA);
+
+        let description = DefaultedEnumConfig::describe_from(&HashMap::<&str, &str>::new());
+        let field = description.field("optional_enum").unwrap();
+        assert_eq!(field.origin, Origin::Default);
+    }
+
+    #[test]
+    fn test_chained_sources() {
+        std::env::set_var("CHAINED_SOURCE_HOST", "env.local");
+        std::env::remove_var("CHAINED_SOURCE_PORT");
+
+        #[derive(ConfigStruct)]
+        struct ChainedConfig {
+            chained_source_host: String,
+            chained_source_port: u16,
+        }
+
+        let defaults = HashMap::from([
+            ("CHAINED_SOURCE_HOST", "defaults.local"),
+            ("CHAINED_SOURCE_PORT", "1"),
+        ]);
+        let overrides = HashMap::from([("CHAINED_SOURCE_PORT", "2")]);
+
+        let config = ChainedConfig::get_from(&Env.or(overrides).or(defaults));
+        assert_eq!(config.chained_source_host, "env.local");
+        assert_eq!(config.chained_source_port, 2);
+    }
+
+    #[allow(dead_code)]
+    struct NoDebug(String);
+
+    impl std::str::FromStr for NoDebug {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(NoDebug(s.to_string()))
+        }
+    }
+
+    #[allow(dead_code)]
+    #[derive(ConfigStruct)]
+    struct DescribedConfig {
+        described_host: String,
+        #[default(5432)]
+        described_port: u16,
+        described_timeout: Option<u32>,
+        #[secret]
+        described_password: String,
+        #[enumerated]
+        mode: SourceMode,
+        #[nested(prefix = "DESCRIBED_DB")]
+        db: NestedDbConfig,
+        #[parse]
+        described_opaque: NoDebug,
+    }
+
+    #[test]
+    fn test_describe() {
+        let source = HashMap::from([
+            ("DESCRIBED_HOST", "example.com"),
+            ("DESCRIBED_PASSWORD", "hunter2"),
+            ("SOURCE_MODE", "fast"),
+            ("DESCRIBED_DB_PORT", "not a port"),
+            ("DESCRIBED_OPAQUE", "opaque"),
+        ]);
+        let description = DescribedConfig::describe_from(&source);
+
+        let fields: Vec<&str> = description
+            .fields()
+            .iter()
+            .map(|r| r.field.as_str())
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                "described_host",
+                "described_port",
+                "described_timeout",
+                "described_password",
+                "mode",
+                "db.host",
+                "db.port",
+                "described_opaque",
+            ]
+        );
+
+        let host = description.field("described_host").unwrap();
+        assert_eq!(host.var_name, "DESCRIBED_HOST");
+        assert_eq!(host.origin, Origin::Variable);
+        assert_eq!(host.raw.as_deref(), Some("example.com"));
+        assert_eq!(host.result, Ok("\"example.com\"".to_string()));
+
+        let port = description.field("described_port").unwrap();
+        assert_eq!(port.origin, Origin::Default);
+        assert_eq!(port.raw.as_deref(), Some("5432"));
+        assert_eq!(port.result, Ok("5432".to_string()));
+
+        let timeout = description.field("described_timeout").unwrap();
+        assert_eq!(timeout.origin, Origin::Unset);
+        assert_eq!(timeout.result, Ok("None".to_string()));
+
+        let mode = description.field("mode").unwrap();
+        assert_eq!(mode.var_name, "SOURCE_MODE");
+        assert_eq!(mode.result, Ok("Fast".to_string()));
+
+        let db_host = description.field("db.host").unwrap();
+        assert_eq!(db_host.var_name, "DESCRIBED_DB_HOST");
+        assert_eq!(db_host.result.as_ref().unwrap_err().field(), "db.host");
+        let db_port = description.field("db.port").unwrap();
+        assert!(matches!(
+            &db_port.result,
+            Err(env_extract::FieldError::Invalid { field, .. }) if field == "db.port"
+        ));
+
+        let opaque = description.field("described_opaque").unwrap();
+        assert_eq!(opaque.result, Ok("<no Debug implementation>".to_string()));
+
+        assert_eq!(
+            description.to_string().lines().take(3).collect::<Vec<_>>(),
+            [
+                "described_host = \"example.com\" (from DESCRIBED_HOST = \"example.com\")",
+                "described_port = 5432 (default \"5432\", DESCRIBED_PORT not set)",
+                "described_timeout = None (DESCRIBED_TIMEOUT not set)",
+            ]
+        );
+    }
+
+    #[allow(dead_code)]
+    #[derive(ConfigStruct)]
+    struct SecretConfig {
+        #[secret]
+        secret_token: String,
+        #[secret]
+        secret_pin: u32,
+    }
+
+    #[test]
+    fn test_secret_fields_are_redacted() {
+        let source = HashMap::from([("SECRET_TOKEN", "hunter2"), ("SECRET_PIN", "12ab")]);
+
+        let description = SecretConfig::describe_from(&source);
+        let token = description.field("secret_token").unwrap();
+        assert!(token.secret);
+        assert_eq!(token.raw.as_deref(), Some("<redacted>"));
+        assert_eq!(token.result, Ok("<redacted>".to_string()));
+
+        let output = description.to_string();
+        assert!(!output.contains("hunter2"));
+        assert!(!output.contains("12ab"));
+
+        let error = SecretConfig::try_get_from(&source).err().unwrap();
+        assert!(!error.to_string().contains("12ab"));
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct SecretWrapperConfig {
+        wrapped_password: Secret<String>,
+        wrapped_pin: Option<Secret<u32>>,
+        #[list(separator = ";")]
+        wrapped_keys: Secret<Vec<String>>,
+    }
+
+    #[test]
+    fn test_secret_wrapper_fields() {
+        let source = HashMap::from([
+            ("WRAPPED_PASSWORD", "hunter2"),
+            ("WRAPPED_PIN", "1234"),
+            ("WRAPPED_KEYS", "key-a;key-b"),
+        ]);
+
+        let config = SecretWrapperConfig::get_from(&source);
+        assert_eq!(config.wrapped_password.expose_secret(), "hunter2");
+        assert_eq!(
+            config.wrapped_pin.as_ref().map(|pin| *pin.expose_secret()),
+            Some(1234)
+        );
+        assert_eq!(config.wrapped_keys.expose_secret(), &["key-a", "key-b"]);
+
+        let debug = format!("{:?}", config);
+        assert!(debug.contains("wrapped_password: Secret(<redacted>)"));
+        assert!(!debug.contains("hunter2"));
+        assert!(!debug.contains("1234"));
+        assert!(!debug.contains("key-a"));
+
+        let description = SecretWrapperConfig::describe_from(&source);
+        assert!(description.fields().iter().all(|report| report.secret));
+        assert!(!description.to_string().contains("hunter2"));
+    }
+
+    #[test]
+    fn test_secret_bool_defaults_to_false() {
+        #[derive(Debug, ConfigStruct)]
+        struct SecretFlagConfig {
+            secret_flag: Secret<bool>,
+        }
+
+        let config = SecretFlagConfig::try_get_from(&HashMap::<&str, &str>::new()).unwrap();
+        assert!(!config.secret_flag.expose_secret());
+        assert!(!SecretFlagConfig::FIELDS[0].required());
+    }
+
+    #[test]
+    fn test_secret_wrapper_parse_error_is_redacted() {
+        let source = HashMap::from([
+            ("WRAPPED_PASSWORD", "hunter2"),
+            ("WRAPPED_PIN", "12ab"),
+            ("WRAPPED_KEYS", ""),
+        ]);
+
+        let errors = SecretWrapperConfig::try_get_from(&source)
+            .unwrap_err()
+            .into_errors();
+        assert!(matches!(
+            errors.as_slice(),
+            [env_extract::FieldError::Invalid { field, value, .. }]
+                if field == "wrapped_pin" && value == "<redacted>"
+        ));
+
+        #[derive(Debug, ConfigStruct)]
+        struct SecretPortsConfig {
+            #[allow(dead_code)]
+            secret_ports: Secret<Vec<u16>>,
+        }
+
+        let source = HashMap::from([("SECRET_PORTS", "80,sup3rsecret")]);
+        let error = SecretPortsConfig::try_get_from(&source).unwrap_err();
+        assert!(!error.to_string().contains("sup3rsecret"));
+        assert!(!format!("{:?}", error).contains("sup3rsecret"));
+        let description = SecretPortsConfig::describe_from(&source);
+        assert!(!description.to_string().contains("sup3rsecret"));
+        assert!(!format!("{:?}", description).contains("sup3rsecret"));
+    }
+
+    mod secrecy_like {
+        /// A secret wrapper from another crate, standing in for `secrecy::Secret`.
+        pub struct Secret<T>(pub T);
+
+        impl std::str::FromStr for Secret<String> {
+            type Err = std::convert::Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(Secret(s.to_string()))
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_foreign_secret() {
+        #[derive(ConfigStruct)]
+        struct ForeignSecretConfig {
+            #[parse]
+            foreign_secret_password: secrecy_like::Secret<String>,
+            #[parse]
+            foreign_secret_pin: Secret<u32>,
+        }
+
+        let source = HashMap::from([
+            ("FOREIGN_SECRET_PASSWORD", "hunter2"),
+            ("FOREIGN_SECRET_PIN", "1234"),
+        ]);
+
+        let config = ForeignSecretConfig::get_from(&source);
+        assert_eq!(config.foreign_secret_password.0, "hunter2");
+        assert_eq!(*config.foreign_secret_pin.expose_secret(), 1234);
+
+        assert!(ForeignSecretConfig::FIELDS.iter().all(|field| field.secret));
+        let description = ForeignSecretConfig::describe_from(&source);
+        assert!(!description.to_string().contains("hunter2"));
+    }
+
+    fn write_secret_file(name: &str, contents: &str) -> String {
+        let path =
+            std::env::temp_dir().join(format!("env-extract-{}-{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    struct FileFieldConfig {
+        #[from_file]
+        file_password: Secret<String>,
+        #[from_file]
+        #[default(5432)]
+        file_port: u16,
+        file_user: String,
+    }
+
+    #[test]
+    fn test_from_file_fields() {
+        let password_path = write_secret_file("password", "hunter2\n");
+        let source = HashMap::from([
+            ("FILE_PASSWORD_FILE", password_path.as_str()),
+            ("FILE_USER", "admin"),
+            ("FILE_USER_FILE", "/nonexistent/ignored"),
+        ]);
+
+        let config = FileFieldConfig::get_from(&source);
+        assert_eq!(config.file_password.expose_secret(), "hunter2");
+        assert_eq!(config.file_port, 5432);
+        assert_eq!(config.file_user, "admin");
+
+        let description = FileFieldConfig::describe_from(&source);
+        assert_eq!(
+            description.field("file_password").unwrap().origin,
+            Origin::File
+        );
+        std::fs::remove_file(&password_path).unwrap();
+    }
+
+    #[test]
+    fn test_from_file_errors() {
+        let source = HashMap::from([
+            ("FILE_PASSWORD", "hunter2"),
+            ("FILE_PASSWORD_FILE", "/run/secrets/password"),
+            ("FILE_PORT_FILE", "/nonexistent/port"),
+            ("FILE_USER", "admin"),
+        ]);
+
+        let errors = FileFieldConfig::try_get_from(&source)
+            .unwrap_err()
+            .into_errors();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0],
+            env_extract::FieldError::File {
+                field: "file_password".to_string(),
+                var_name: "FILE_PASSWORD".to_string(),
+                message: "both FILE_PASSWORD and FILE_PASSWORD_FILE are set, only one of them may be used"
+                    .to_string(),
+            }
+        );
+        assert!(matches!(
+            &errors[1],
+            env_extract::FieldError::File { field, message, .. }
+                if field == "file_port" && message.starts_with("could not read FILE_PORT_FILE")
+        ));
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    #[from_file]
+    struct AllFromFileConfig {
+        all_file_token: String,
+        all_file_keys: Vec<String>,
+    }
+
+    #[test]
+    fn test_from_file_on_struct() {
+        let token_path = write_secret_file("token", "abc\r\n");
+        let keys_path = write_secret_file("keys", "a,b\n\n");
+        let source = HashMap::from([
+            ("ALL_FILE_TOKEN_FILE", token_path.as_str()),
+            ("ALL_FILE_KEYS_FILE", keys_path.as_str()),
+        ]);
+
+        let config = AllFromFileConfig::get_from(&source);
+        std::fs::remove_file(&token_path).unwrap();
+        std::fs::remove_file(&keys_path).unwrap();
+
+        assert_eq!(config.all_file_token, "abc");
+        assert_eq!(config.all_file_keys, ["a", "b"]);
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct DurationConfig {
+        duration_timeout: Duration,
+        #[duration(unit = "ms")]
+        duration_poll: Duration,
+        #[default("1h30m")]
+        duration_ttl: Duration,
+        duration_grace: Option<std::time::Duration>,
+        duration_backoff: Vec<Duration>,
+    }
+
+    #[test]
+    fn test_duration_fields() {
+        let source = HashMap::from([
+            ("DURATION_TIMEOUT", "30"),
+            ("DURATION_POLL", "250"),
+            ("DURATION_GRACE", "2d"),
+            ("DURATION_BACKOFF", "500ms, 1s, 1m 30s"),
+        ]);
+
+        let config = DurationConfig::get_from(&source);
+        assert_eq!(config.duration_timeout, Duration::from_secs(30));
+        assert_eq!(config.duration_poll, Duration::from_millis(250));
+        assert_eq!(config.duration_ttl, Duration::from_secs(90 * 60));
+        assert_eq!(
+            config.duration_grace,
+            Some(Duration::from_secs(2 * 24 * 60 * 60))
+        );
+        assert_eq!(
+            config.duration_backoff,
+            [
+                Duration::from_millis(500),
+                Duration::from_secs(1),
+                Duration::from_secs(90)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duration_field_errors() {
+        let source = HashMap::from([
+            ("DURATION_TIMEOUT", "30 fortnights"),
+            ("DURATION_POLL", "99999999999999999999"),
+            ("DURATION_BACKOFF", "1s,h"),
+        ]);
+
+        let errors = DurationConfig::try_get_from(&source)
+            .unwrap_err()
+            .into_errors();
+        let messages: Vec<String> = errors
+            .iter()
+            .map(|error| match error {
+                env_extract::FieldError::Invalid { message, .. } => message.clone(),
+                other => panic!("unexpected error {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "unknown unit \"fortnights\", expected one of ns, us, ms, s, m, h or d",
+                "99999999999999999999 is too large",
+                "invalid item \"h\": expected a number at \"h\"",
+            ]
+        );
+    }
+
+    mod chrono_like {
+        /// A signed duration in seconds, standing in for types such as `chrono::Duration`.
+        #[derive(Debug, PartialEq)]
+        pub struct Duration(pub i64);
+
+        impl std::str::FromStr for Duration {
+            type Err = std::num::ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse().map(Duration)
+            }
+        }
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct ParsedDurationConfig {
+        #[parse]
+        parsed_duration_offset: chrono_like::Duration,
+        #[parse]
+        parsed_duration_skew: Option<chrono_like::Duration>,
+        parsed_duration_timeout: core::time::Duration,
+    }
+
+    #[test]
+    fn test_parse_non_std_duration() {
+        let source = HashMap::from([
+            ("PARSED_DURATION_OFFSET", "-90"),
+            ("PARSED_DURATION_TIMEOUT", "5s"),
+        ]);
+
+        let config = ParsedDurationConfig::get_from(&source);
+        assert_eq!(config.parsed_duration_offset, chrono_like::Duration(-90));
+        assert_eq!(config.parsed_duration_skew, None);
+        assert_eq!(config.parsed_duration_timeout, Duration::from_secs(5));
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct ByteSizeConfig {
+        #[bytes]
+        bytes_cache: u64,
+        #[bytes]
+        #[default("1MiB")]
+        bytes_body_limit: usize,
+        #[bytes]
+        bytes_buffer: Option<u16>,
+        #[bytes]
+        bytes_tiers: Vec<u64>,
+    }
+
+    #[test]
+    fn test_byte_size_fields() {
+        let source = HashMap::from([
+            ("BYTES_CACHE", "512MiB"),
+            ("BYTES_BUFFER", "4k"),
+            ("BYTES_TIERS", "1GB, 2 gib, 100"),
+        ]);
+
+        let config = ByteSizeConfig::get_from(&source);
+        assert_eq!(config.bytes_cache, 512 * 1024 * 1024);
+        assert_eq!(config.bytes_body_limit, 1024 * 1024);
+        assert_eq!(config.bytes_buffer, Some(4000));
+        assert_eq!(
+            config.bytes_tiers,
+            [1_000_000_000, 2 * 1024 * 1024 * 1024, 100]
+        );
+    }
+
+    #[test]
+    fn test_byte_size_overflow() {
+        let source = HashMap::from([
+            ("BYTES_CACHE", "20EiB"),
+            ("BYTES_BUFFER", "64KiB"),
+            ("BYTES_TIERS", "1PB,0.5B"),
+        ]);
+
+        let errors = ByteSizeConfig::try_get_from(&source)
+            .unwrap_err()
+            .into_errors();
+        let messages: Vec<String> = errors
+            .iter()
+            .map(|error| match error {
+                env_extract::FieldError::Invalid { message, .. } => message.clone(),
+                other => panic!("unexpected error {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "23058430092136939520 bytes does not fit in u64",
+                "65536 bytes does not fit in u16",
+                "invalid item \"0.5B\": 0.5B is not a whole number of bytes",
+            ]
+        );
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct BoolConfig {
+        bool_spelling: bool,
+        #[default(true)]
+        bool_defaulted: bool,
+        #[default("on")]
+        bool_defaulted_string: bool,
+        bool_unset: bool,
+        bool_flags: Vec<bool>,
+    }
+
+    #[test]
+    fn test_bool_spellings_and_defaults() {
+        for (value, expected) in [
+            ("true", true),
+            ("TRUE", true),
+            ("Yes", true),
+            ("on", true),
+            ("1", true),
+            ("false", false),
+            ("No", false),
+            ("OFF", false),
+            ("0", false),
+        ] {
+            let source = HashMap::from([("BOOL_SPELLING", value), ("BOOL_FLAGS", "")]);
+            let config = BoolConfig::get_from(&source);
+            assert_eq!(config.bool_spelling, expected, "{}", value);
+            assert!(config.bool_defaulted);
+            assert!(config.bool_defaulted_string);
+            assert!(!config.bool_unset);
+        }
+
+        let source = HashMap::from([
+            ("BOOL_SPELLING", "yes"),
+            ("BOOL_DEFAULTED", "no"),
+            ("BOOL_FLAGS", "on, off, 1"),
+        ]);
+        let config = BoolConfig::get_from(&source);
+        assert!(!config.bool_defaulted);
+        assert_eq!(config.bool_flags, [true, false, true]);
+    }
+
+    #[test]
+    fn test_bool_invalid_value() {
+        let source = HashMap::from([("BOOL_SPELLING", "maybe"), ("BOOL_FLAGS", "")]);
+
+        let errors = BoolConfig::try_get_from(&source).unwrap_err().into_errors();
+        assert_eq!(
+            errors,
+            [env_extract::FieldError::Invalid {
+                field: "bool_spelling".to_string(),
+                var_name: "BOOL_SPELLING".to_string(),
+                value: "maybe".to_string(),
+                message: "expected one of true, false, yes, no, on, off, 1 or 0".to_string(),
+            }]
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    fn validate_bucket(value: &str) -> Result<(), String> {
+        if value.starts_with("s3://") {
+            Ok(())
+        } else {
+            Err("must start with s3://".to_string())
+        }
+    }
+
+    #[cfg(feature = "regex")]
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    struct ValidatedConfig {
+        #[validate(range(min = 1, max = 65535))]
+        validated_port: u32,
+        #[default("api")]
+        #[validate(non_empty, length(max = 8), regex = "^[a-z]+$")]
+        validated_name: String,
+        #[validate(one_of("debug", "info", "warn"))]
+        validated_level: Option<String>,
+        #[validate(length(min = 1), range(min = 0.0, max = 1.0))]
+        validated_ratios: Vec<f64>,
+        #[validate(with = validate_bucket)]
+        validated_bucket: Secret<String>,
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_validated_fields() {
+        let source = HashMap::from([
+            ("VALIDATED_PORT", "8080"),
+            ("VALIDATED_LEVEL", "info"),
+            ("VALIDATED_RATIOS", "0.5,1"),
+            ("VALIDATED_BUCKET", "s3://backups"),
+        ]);
+        let config = ValidatedConfig::get_from(&source);
+        assert_eq!(config.validated_port, 8080);
+        assert_eq!(config.validated_name, "api");
+        assert_eq!(config.validated_level.as_deref(), Some("info"));
+        assert_eq!(config.validated_ratios, [0.5, 1.0]);
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_validation_errors() {
+        let source = HashMap::from([
+            ("VALIDATED_PORT", "0"),
+            ("VALIDATED_NAME", "Api"),
+            ("VALIDATED_LEVEL", "trace"),
+            ("VALIDATED_RATIOS", "0.5,1.5"),
+            ("VALIDATED_BUCKET", "gs://backups"),
+        ]);
+
+        let errors = ValidatedConfig::try_get_from(&source)
+            .unwrap_err()
+            .into_errors();
+        let invalid = |field: &str, var_name: &str, value: &str, message: &str| {
+            env_extract::FieldError::Invalid {
+                field: field.to_string(),
+                var_name: var_name.to_string(),
+                value: value.to_string(),
+                message: message.to_string(),
+            }
+        };
+        assert_eq!(
+            errors,
+            [
+                invalid(
+                    "validated_port",
+                    "VALIDATED_PORT",
+                    "0",
+                    "must be between 1 and 65535"
+                ),
+                invalid(
+                    "validated_name",
+                    "VALIDATED_NAME",
+                    "Api",
+                    "must match the pattern \"^[a-z]+$\""
+                ),
+                invalid(
+                    "validated_level",
+                    "VALIDATED_LEVEL",
+                    "trace",
+                    "must be one of debug, info, warn"
+                ),
+                invalid(
+                    "validated_ratios",
+                    "VALIDATED_RATIOS",
+                    "0.5,1.5",
+                    "must be between 0.0 and 1.0"
+                ),
+                invalid(
+                    "validated_bucket",
+                    "VALIDATED_BUCKET",
+                    "<redacted>",
+                    "failed validation, the details are hidden because the field is secret"
+                ),
+            ]
+        );
+
+        let source = HashMap::from([
+            ("VALIDATED_PORT", "80"),
+            ("VALIDATED_NAME", ""),
+            ("VALIDATED_RATIOS", ""),
+            ("VALIDATED_BUCKET", "s3://backups"),
+        ]);
+        let errors = ValidatedConfig::try_get_from(&source)
+            .unwrap_err()
+            .into_errors();
+        assert_eq!(
+            errors,
+            [
+                invalid("validated_name", "VALIDATED_NAME", "", "must not be empty"),
+                invalid(
+                    "validated_ratios",
+                    "VALIDATED_RATIOS",
+                    "",
+                    "must have a length of at least 1, found 0"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duration_range() {
+        #[derive(Debug, ConfigStruct)]
+        struct DurationRangeConfig {
+            #[duration(unit = "ms")]
+            #[validate(range(min = Duration::from_millis(100), max = Duration::from_secs(30)))]
+            validated_timeout: Duration,
+        }
+
+        let source = HashMap::from([("VALIDATED_TIMEOUT", "1500")]);
+        let config = DurationRangeConfig::try_get_from(&source).unwrap();
+        assert_eq!(config.validated_timeout, Duration::from_millis(1500));
+
+        let source = HashMap::from([("VALIDATED_TIMEOUT", "1m")]);
+        let errors = DurationRangeConfig::try_get_from(&source)
+            .unwrap_err()
+            .into_errors();
+        assert_eq!(
+            errors,
+            [env_extract::FieldError::Invalid {
+                field: "validated_timeout".to_string(),
+                var_name: "VALIDATED_TIMEOUT".to_string(),
+                value: "1m".to_string(),
+                message: "must be between 100ms and 30s".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_secret_validation_message_is_redacted() {
+        #[allow(dead_code)]
+        #[derive(Debug, ConfigStruct)]
+        struct SecretPinConfig {
+            #[secret]
+            #[validate(length(max = 3))]
+            validated_secret_pin: String,
+        }
+
+        let source = HashMap::from([("VALIDATED_SECRET_PIN", "1234567")]);
+        let error = SecretPinConfig::try_get_from(&source).unwrap_err();
+        assert_eq!(
+            error.errors(),
+            [env_extract::FieldError::Invalid {
+                field: "validated_secret_pin".to_string(),
+                var_name: "VALIDATED_SECRET_PIN".to_string(),
+                value: "<redacted>".to_string(),
+                message: "failed validation, the details are hidden because the field is secret"
+                    .to_string(),
+            }]
+        );
+        assert!(!error.to_string().contains('7'));
+    }
+
+    fn check_pool(config: &PoolConfig) -> Result<(), String> {
+        if config.rule_min_pool <= config.rule_max_pool {
+            Ok(())
+        } else {
+            Err(format!(
+                "RULE_MIN_POOL ({}) must not be greater than RULE_MAX_POOL ({})",
+                config.rule_min_pool, config.rule_max_pool
+            ))
+        }
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    #[config(validate = "check_pool")]
+    struct PoolConfig {
+        #[default(1)]
+        rule_min_pool: u32,
+        #[default(10)]
+        rule_max_pool: u32,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    struct RuleConfig {
+        rule_use_tls: bool,
+        #[required_if(rule_use_tls = true)]
+        rule_tls_cert: Option<String>,
+        rule_password: Option<String>,
+        #[conflicts_with(rule_password)]
+        rule_password_file: Option<String>,
+        rule_proxy: Option<String>,
+        #[required_if(rule_proxy)]
+        rule_proxy_port: Option<u16>,
+        #[nested]
+        rule_pool: PoolConfig,
+    }
+
+    #[test]
+    fn test_cross_field_rules() {
+        let source = HashMap::from([
+            ("RULE_USE_TLS", "true"),
+            ("RULE_TLS_CERT", "/etc/tls/cert.pem"),
+            ("RULE_PASSWORD", "hunter2"),
+            ("RULE_PROXY", "proxy.internal"),
+            ("RULE_PROXY_PORT", "3128"),
+            ("RULE_POOL_RULE_MAX_POOL", "4"),
+        ]);
+        let config = RuleConfig::get_from(&source);
+        assert_eq!(config.rule_tls_cert.as_deref(), Some("/etc/tls/cert.pem"));
+        assert_eq!(config.rule_pool.rule_max_pool, 4);
+
+        let config = RuleConfig::get_from(&HashMap::<&str, &str>::new());
+        assert_eq!(config.rule_tls_cert, None);
+    }
+
+    #[test]
+    fn test_cross_field_rule_errors() {
+        let source = HashMap::from([
+            ("RULE_USE_TLS", "yes"),
+            ("RULE_PASSWORD", "hunter2"),
+            ("RULE_PASSWORD_FILE", "/run/secrets/password"),
+            ("RULE_PROXY", "proxy.internal"),
+            ("RULE_POOL_RULE_MIN_POOL", "5"),
+            ("RULE_POOL_RULE_MAX_POOL", "2"),
+        ]);
+
+        let error = RuleConfig::try_get_from(&source).unwrap_err();
+        let constraint = |field: &str, message: &str| env_extract::FieldError::Constraint {
+            field: field.to_string(),
+            message: message.to_string(),
+        };
+        assert_eq!(
+            error.errors(),
+            [
+                constraint(
+                    "rule_pool",
+                    "RULE_MIN_POOL (5) must not be greater than RULE_MAX_POOL (2)"
+                ),
+                constraint(
+                    "rule_tls_cert",
+                    "RULE_TLS_CERT must be set when RULE_USE_TLS is true"
+                ),
+                constraint(
+                    "rule_password_file",
+                    "RULE_PASSWORD_FILE cannot be set together with RULE_PASSWORD"
+                ),
+                constraint(
+                    "rule_proxy_port",
+                    "RULE_PROXY_PORT must be set when RULE_PROXY is set"
+                ),
+            ]
+        );
+
+        let source = HashMap::from([
+            ("RULE_USE_TLS", "yes"),
+            ("RULE_PASSWORD", "hunter2"),
+            ("RULE_PASSWORD_FILE", "/run/secrets/password"),
+            ("RULE_PROXY", "proxy.internal"),
+        ]);
+        let error = RuleConfig::try_get_from(&source).unwrap_err();
+        assert_eq!(
+            error.errors(),
+            [
+                constraint(
+                    "rule_tls_cert",
+                    "RULE_TLS_CERT must be set when RULE_USE_TLS is true"
+                ),
+                constraint(
+                    "rule_password_file",
+                    "RULE_PASSWORD_FILE cannot be set together with RULE_PASSWORD"
+                ),
+                constraint(
+                    "rule_proxy_port",
+                    "RULE_PROXY_PORT must be set when RULE_PROXY is set"
+                ),
+            ]
+        );
+        assert_eq!(
+            error.errors()[0].to_string(),
+            "Invalid configuration for 'rule_tls_cert': RULE_TLS_CERT must be set when RULE_USE_TLS is true"
+        );
+
+        let error =
+            PoolConfig::try_get_from(&HashMap::from([("RULE_MIN_POOL", "11")])).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid configuration: RULE_MIN_POOL (11) must not be greater than RULE_MAX_POOL (10)"
+        );
+    }
+
+    #[derive(Debug, EnvVar)]
+    #[var_name = "RULE_MODE"]
+    #[case(convert = "lowercase")]
+    enum RuleMode {
+        Plain,
+        Tls,
+        #[default]
+        Auto,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    struct TypedRuleConfig {
+        rule_opt_tls: Option<bool>,
+        #[required_if(rule_opt_tls = true)]
+        rule_opt_cert: Option<String>,
+        #[enumerated]
+        rule_mode: RuleMode,
+        #[required_if(rule_mode = "tls")]
+        rule_mode_cert: Option<String>,
+        rule_opt_workers: Option<u16>,
+        #[required_if(rule_opt_workers = 0)]
+        rule_opt_executor: Option<String>,
+    }
+
+    #[test]
+    fn test_required_if_on_optional_and_enum_fields() {
+        let config = TypedRuleConfig::get_from(&HashMap::from([
+            ("RULE_OPT_TLS", "false"),
+            ("RULE_MODE", "plain"),
+            ("RULE_OPT_WORKERS", "4"),
+        ]));
+        assert_eq!(config.rule_opt_tls, Some(false));
+        assert!(matches!(config.rule_mode, RuleMode::Plain));
+        assert!(TypedRuleConfig::try_get_from(&HashMap::<&str, &str>::new()).is_ok());
+
+        let source = HashMap::from([
+            ("RULE_OPT_TLS", "true"),
+            ("RULE_MODE", "tls"),
+            ("RULE_OPT_WORKERS", "0"),
+        ]);
+        let error = TypedRuleConfig::try_get_from(&source).unwrap_err();
+        let messages: Vec<String> = error
+            .errors()
+            .iter()
+            .map(|error| match error {
+                env_extract::FieldError::Constraint { message, .. } => message.clone(),
+                other => panic!("unexpected error {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "RULE_OPT_CERT must be set when RULE_OPT_TLS is true",
+                "RULE_MODE_CERT must be set when RULE_MODE is \"tls\"",
+                "RULE_OPT_EXECUTOR must be set when RULE_OPT_WORKERS is 0",
+            ]
+        );
+
+        let source = HashMap::from([
+            ("RULE_OPT_TLS", "true"),
+            ("RULE_OPT_CERT", "/etc/tls/cert.pem"),
+            ("RULE_MODE", "tls"),
+            ("RULE_MODE_CERT", "/etc/tls/cert.pem"),
+        ]);
+        assert!(TypedRuleConfig::try_get_from(&source).is_ok());
+    }
+
+    #[derive(Debug, PartialEq, EnvVar)]
+    #[var_name(primary = "ALIAS_MODE", aliases = ["ALIAS_LEGACY_MODE"])]
+    #[case(convert = "lowercase")]
+    enum AliasMode {
+        Fast,
+        Safe,
+        Invalid,
+    }
+
+    #[derive(Debug, ConfigStruct)]
+    struct AliasConfig {
+        #[var_name(primary = "ALIAS_DB_URL", aliases = ["ALIAS_DATABASE_URL", "ALIAS_PG_URL"])]
+        db_url: String,
+        #[default(5)]
+        #[var_name(aliases = ["ALIAS_OLD_RETRIES"], deprecated)]
+        alias_retries: u32,
+        #[enumerated]
+        mode: AliasMode,
+    }
+
+    #[test]
+    fn test_var_name_aliases() {
+        let source = HashMap::from([
+            ("ALIAS_DB_URL", "postgres://new"),
+            ("ALIAS_DATABASE_URL", "postgres://old"),
+            ("ALIAS_LEGACY_MODE", "safe"),
+        ]);
+        let config = AliasConfig::get_from(&source);
+        assert_eq!(config.db_url, "postgres://new");
+        assert_eq!(config.alias_retries, 5);
+        assert_eq!(config.mode, AliasMode::Safe);
+
+        let source = HashMap::from([
+            ("ALIAS_PG_URL", "postgres://pg"),
+            ("ALIAS_OLD_RETRIES", "3"),
+            ("ALIAS_MODE", "fast"),
+            ("ALIAS_LEGACY_MODE", "safe"),
+        ]);
+        let config = AliasConfig::get_from(&source);
+        assert_eq!(config.db_url, "postgres://pg");
+        assert_eq!(config.alias_retries, 3);
+        assert_eq!(config.mode, AliasMode::Fast);
+
+        let description = AliasConfig::describe_from(&source);
+        assert_eq!(
+            description.field("db_url").unwrap().var_name,
+            "ALIAS_PG_URL"
+        );
+        assert_eq!(
+            description.field("alias_retries").unwrap().var_name,
+            "ALIAS_OLD_RETRIES"
+        );
+    }
+
+    #[test]
+    fn test_var_name_alias_errors() {
+        let source = HashMap::from([
+            ("ALIAS_DATABASE_URL", "postgres://old"),
+            ("ALIAS_OLD_RETRIES", "many"),
+        ]);
+        let errors = AliasConfig::try_get_from(&source)
+            .unwrap_err()
+            .into_errors();
+        assert_eq!(
+            errors,
+            [env_extract::FieldError::Invalid {
+                field: "alias_retries".to_string(),
+                var_name: "ALIAS_OLD_RETRIES".to_string(),
+                value: "many".to_string(),
+                message: "invalid digit found in string".to_string(),
+            },]
+        );
+
+        assert_eq!(
+            AliasMode::try_get_from(&HashMap::from([("ALIAS_LEGACY_MODE", "slow")])).err(),
+            Some(env_extract::EnvVarError::Invalid {
+                var_name: "ALIAS_LEGACY_MODE".to_string(),
+                value: "slow".to_string(),
+                expected: &["fast", "safe", "invalid"],
+            })
+        );
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    struct ExampleDbConfig {
+        /// Database host.
+        host: String,
+        #[default(5432)]
+        port: u16,
+        #[secret]
+        #[default("changeme")]
+        #[from_file]
+        password: String,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    #[env_prefix = "EXAMPLE_"]
+    struct ExampleConfig {
+        /// Name reported in logs.
+        ///
+        /// Defaults to the binary name.
+        name: Option<String>,
+        #[default("a b")]
+        greeting: String,
+        verbose: bool,
+        #[var_name(primary = "EXAMPLE_TAGS", aliases = ["EXAMPLE_LABELS"])]
+        tags: Vec<String>,
+        #[nested]
+        db: ExampleDbConfig,
+        #[enumerated]
+        mode: AliasMode,
+    }
+
+    #[test]
+    fn test_env_example() {
+        assert_eq!(
+            ExampleConfig::env_example(),
+            "\
+# Name reported in logs.
+#
+# Defaults to the binary name.
+# EXAMPLE_NAME=
+
+# EXAMPLE_GREETING=\"a b\"
+
+# EXAMPLE_VERBOSE=
+
+# Also read from: EXAMPLE_LABELS
+EXAMPLE_TAGS=
+
+# Database host.
+EXAMPLE_DB_HOST=
+
+# EXAMPLE_DB_PORT=5432
+
+# Can be read from the file named by EXAMPLE_DB_PASSWORD_FILE.
+# EXAMPLE_DB_PASSWORD=
+
+# One of: fast, safe
+# Also read from: EXAMPLE_ALIAS_LEGACY_MODE
+# EXAMPLE_ALIAS_MODE=
+"
+        );
+    }
+
+    #[test]
+    fn test_env_example_enum_default() {
+        assert_eq!(
+            DefaultedEnumConfig::env_example(),
+            "# One of: VariantA, VariantB\n# OPTIONAL_ENUM=VariantB\n"
+        );
+        assert!(DefaultedEnumConfig::try_get_from(&HashMap::<&str, &str>::new()).is_ok());
+
+        assert_eq!(
+            PanickingEnumConfig::env_example(),
+            "# One of: VariantA, VariantB\nOPTIONAL_ENUM=\n"
+        );
+        assert!(PanickingEnumConfig::try_get_from(&HashMap::<&str, &str>::new()).is_err());
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    #[env_prefix = "TABLE_"]
+    struct MarkdownConfig {
+        /// Port to listen on.
+        #[default(8080)]
+        #[validate(range(min = 1, max = 65535))]
+        port: u16,
+        /// Hosts allowed to connect,
+        /// such as `a|b`.
+        #[list(separator = ";")]
+        #[validate(non_empty, one_of("a", "b"))]
+        hosts: Vec<String>,
+        #[secret]
+        #[default("hunter2")]
+        token: String,
+        #[parse]
+        path: Option<std::path::PathBuf>,
+        #[nested]
+        db: ExampleDbConfig,
+        #[enumerated]
+        mode: AliasMode,
+    }
+
+    #[test]
+    fn test_markdown_table() {
+        assert_eq!(
+            MarkdownConfig::markdown_table(),
+            "\
+| Variable | Type | Default | Allowed values | Description | Secret |
+| --- | --- | --- | --- | --- | --- |
+| `TABLE_PORT` | integer | `8080` | 1 to 65535 | Port to listen on. | no |
+| `TABLE_HOSTS` | list of string, separated by `;` | *required* | not empty; each item `a`, `b` | Hosts allowed to connect, such as `a\\|b`. | no |
+| `TABLE_TOKEN` | string | *redacted* |  |  | yes |
+| `TABLE_PATH` | `std::path::PathBuf` |  |  |  | no |
+| `TABLE_DB_HOST` | string | *required* |  | Database host. | no |
+| `TABLE_DB_PORT` | integer | `5432` |  |  | no |
+| `TABLE_DB_PASSWORD` | string | *redacted* |  | Can be read from the file named by `TABLE_DB_PASSWORD_FILE`. | yes |
+| `TABLE_ALIAS_MODE` | enum |  | `fast`, `safe` | Also read from `TABLE_ALIAS_LEGACY_MODE`. | no |
+"
+        );
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    struct BoundsConfig {
+        #[validate(range(min = Duration::from_secs(1), max = Duration::from_secs(60 * 60)))]
+        bounds_timeout: Duration,
+        #[validate(range(min = -1, max = u16::MAX as i32))]
+        bounds_offset: i32,
+    }
+
+    #[test]
+    fn test_markdown_expression_bounds() {
+        use env_extract::Constraint;
+
+        assert_eq!(
+            BoundsConfig::FIELDS[0].constraints,
+            &[Constraint::Range {
+                min: Some("Duration::from_secs(1)"),
+                max: Some("Duration::from_secs(60 * 60)"),
+            }]
+        );
+        assert_eq!(
+            BoundsConfig::markdown_table(),
+            "\
+| Variable | Type | Default | Allowed values | Description | Secret |
+| --- | --- | --- | --- | --- | --- |
+| `BOUNDS_TIMEOUT` | duration | *required* | `Duration::from_secs(1)` to `Duration::from_secs(60 * 60)` |  | no |
+| `BOUNDS_OFFSET` | integer | *required* | -1 to `u16::MAX as i32` |  | no |
+"
+        );
+    }
+
+    #[test]
+    fn test_field_metadata() {
+        use env_extract::{Constraint, FieldKind, VariantInfo};
+
+        let names: Vec<String> = MarkdownConfig::FIELDS
+            .iter()
+            .map(|field| field.var_name_with_prefix(MarkdownConfig::ENV_PREFIX))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "TABLE_PORT",
+                "TABLE_HOSTS",
+                "TABLE_TOKEN",
+                "TABLE_PATH",
+                "TABLE_DB_",
+                "TABLE_ALIAS_MODE"
+            ]
+        );
+
+        let hosts = &MarkdownConfig::FIELDS[1];
+        assert_eq!(hosts.name, "hosts");
+        assert_eq!(
+            hosts.kind,
+            FieldKind::List {
+                item: &FieldKind::String,
+                separator: ";"
+            }
+        );
+        assert_eq!(
+            hosts.constraints,
+            &[Constraint::NonEmpty, Constraint::OneOf(&["a", "b"])]
+        );
+        assert!(hosts.required());
+
+        let path = &MarkdownConfig::FIELDS[3];
+        assert_eq!(path.kind, FieldKind::FromStr);
+        assert!(path.optional && !path.required());
+
+        assert_eq!(
+            MarkdownConfig::FIELDS[4].kind,
+            FieldKind::Nested {
+                fields: ExampleDbConfig::FIELDS
+            }
+        );
+        assert_eq!(
+            MarkdownConfig::FIELDS[5].kind,
+            FieldKind::Enum {
+                variants: AliasMode::VARIANTS
+            }
+        );
+        assert_eq!(
+            AliasMode::VARIANTS,
+            &[
+                VariantInfo {
+                    name: "Fast",
+                    value: "fast",
+                    any_case: false,
+                    fallback: false
+                },
+                VariantInfo {
+                    name: "Safe",
+                    value: "safe",
+                    any_case: false,
+                    fallback: false
+                },
+                VariantInfo {
+                    name: "Invalid",
+                    value: "invalid",
+                    any_case: false,
+                    fallback: true
+                },
+            ]
+        );
+        assert_eq!(
+            AliasMode::try_get_from(&HashMap::from([("ALIAS_MODE", "invalid")])),
+            Ok(AliasMode::Invalid)
+        );
+    }
 }
@@ -0,0 +1,143 @@
+#![cfg(all(feature = "toml", feature = "json"))]
+
+use std::collections::HashMap;
+
+use env_extract::dotenv::Dotenv;
+use env_extract::file::{ConfigFile, ConfigFileError};
+use env_extract::{ConfigStruct, Env, FieldError, Layered};
+
+#[test]
+fn test_parse_toml() {
+    let file = ConfigFile::parse_toml(
+        r#"
+        name = "app"
+        debug = true
+        ratio = 0.5
+        tags = ["a", "b"]
+
+        [db]
+        port = 5432
+
+        [db.replica]
+        host = "replica.local"
+        "#,
+    )
+    .unwrap();
+
+    let vars: Vec<(&str, &str)> = file.iter().collect();
+    assert_eq!(
+        vars,
+        [
+            ("DB_PORT", "5432"),
+            ("DB_REPLICA_HOST", "replica.local"),
+            ("DEBUG", "true"),
+            ("NAME", "app"),
+            ("RATIO", "0.5"),
+            ("TAGS", "a,b"),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_json() {
+    let file =
+        ConfigFile::parse_json(r#"{"db": {"host": "localhost", "ports": [1, 2]}, "unset": null}"#)
+            .unwrap();
+
+    assert_eq!(file.get("DB_HOST"), Some("localhost"));
+    assert_eq!(file.get("DB_PORTS"), Some("1,2"));
+    assert_eq!(file.get("UNSET"), None);
+}
+
+#[test]
+fn test_parse_file_errors() {
+    assert!(matches!(
+        ConfigFile::parse_toml("name = "),
+        Err(ConfigFileError::Syntax { .. })
+    ));
+    assert!(matches!(
+        ConfigFile::parse_json("[1, 2]"),
+        Err(ConfigFileError::Syntax { .. })
+    ));
+    assert!(matches!(
+        ConfigFile::parse_toml("[[servers]]\nhost = \"a\""),
+        Err(ConfigFileError::Unsupported { key, .. }) if key == "SERVERS"
+    ));
+    assert!(matches!(
+        ConfigFile::json("/nonexistent/config.json"),
+        Err(ConfigFileError::Io { .. })
+    ));
+}
+
+#[derive(Debug, ConfigStruct)]
+struct LayeredConfig {
+    layered_host: String,
+    layered_port: u16,
+    layered_user: String,
+    #[default("info")]
+    layered_log: String,
+}
+
+#[test]
+fn test_layer_precedence_and_origins() {
+    std::env::set_var("LAYERED_USER", "from-env");
+    std::env::remove_var("LAYERED_HOST");
+    std::env::remove_var("LAYERED_PORT");
+    std::env::remove_var("LAYERED_LOG");
+
+    let loaded = Layered::new()
+        .layer(
+            "defaults",
+            HashMap::from([
+                ("LAYERED_HOST", "localhost"),
+                ("LAYERED_PORT", "80"),
+                ("LAYERED_USER", "nobody"),
+            ]),
+        )
+        .layer(
+            "config.toml",
+            ConfigFile::parse_toml("[layered]\nport = 8080\nuser = \"file\"").unwrap(),
+        )
+        .layer(".env", Dotenv::parse("LAYERED_PORT=9090").unwrap())
+        .layer("env", Env)
+        .load::<LayeredConfig>()
+        .unwrap();
+
+    assert_eq!(loaded.value.layered_host, "localhost");
+    assert_eq!(loaded.value.layered_port, 9090);
+    assert_eq!(loaded.value.layered_user, "from-env");
+    assert_eq!(loaded.value.layered_log, "info");
+
+    assert_eq!(loaded.origin("LAYERED_HOST"), Some("defaults"));
+    assert_eq!(loaded.origin("LAYERED_PORT"), Some(".env"));
+    assert_eq!(loaded.origin("LAYERED_USER"), Some("env"));
+    assert_eq!(loaded.origin("LAYERED_LOG"), None);
+}
+
+#[test]
+fn test_layered_errors_and_lookup() {
+    let layered = Layered::new()
+        .layer("base", HashMap::from([("LAYERED_ERR_PORT", "1")]))
+        .layer(
+            "override",
+            HashMap::from([("LAYERED_ERR_PORT", "not a port")]),
+        );
+
+    assert_eq!(
+        layered.lookup("LAYERED_ERR_PORT"),
+        Some(("not a port".to_string(), "override"))
+    );
+    assert_eq!(layered.origin("LAYERED_ERR_HOST"), None);
+
+    #[allow(dead_code)]
+    #[derive(Debug, ConfigStruct)]
+    struct Config {
+        layered_err_port: u16,
+    }
+
+    let errors = layered.load::<Config>().unwrap_err().into_errors();
+    assert!(matches!(
+        errors.as_slice(),
+        [FieldError::Invalid { value, .. }] if value == "not a port"
+    ));
+}
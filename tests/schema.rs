@@ -0,0 +1,159 @@
+#![cfg(all(feature = "json", feature = "regex"))]
+
+use env_extract::{ConfigStruct, EnvVar, Secret};
+use serde_json::json;
+use std::collections::HashMap;
+
+#[allow(dead_code)]
+#[derive(EnvVar)]
+#[var_name = "LOG_FORMAT"]
+#[case(convert = "lowercase")]
+enum LogFormat {
+    Text,
+    #[case(convert = "any")]
+    Json,
+    #[default]
+    Invalid,
+}
+
+#[allow(dead_code)]
+#[derive(ConfigStruct)]
+struct TlsConfig {
+    /// Path to the certificate.
+    cert: Option<String>,
+    #[default(false)]
+    verify: bool,
+}
+
+#[allow(dead_code)]
+#[derive(ConfigStruct)]
+#[env_prefix = "SCHEMA_"]
+struct Config {
+    /// Address to listen on.
+    #[validate(regex = "^[^:]+:[0-9]+$")]
+    listen: String,
+    #[default(0.5)]
+    #[validate(range(min = 0.0, max = 1.0))]
+    sample_rate: f64,
+    #[default("a, b")]
+    #[validate(length(max = 4), one_of("a", "b", "c"))]
+    tags: Vec<String>,
+    #[bytes]
+    #[default("64MiB")]
+    max_body: u64,
+    password: Secret<String>,
+    #[secret]
+    #[default("hunter2")]
+    token: String,
+    #[enumerated]
+    log_format: LogFormat,
+    #[nested]
+    tls: TlsConfig,
+}
+
+#[test]
+fn test_config_schema() {
+    assert_eq!(
+        Config::json_schema(),
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "SCHEMA_LISTEN": {
+                    "type": "string",
+                    "description": "Address to listen on.",
+                    "pattern": "^[^:]+:[0-9]+$",
+                },
+                "SCHEMA_SAMPLE_RATE": {
+                    "type": "number",
+                    "default": 0.5,
+                    "minimum": 0.0,
+                    "maximum": 1.0,
+                },
+                "SCHEMA_TAGS": {
+                    "type": "string",
+                    "description": "Items are separated by \",\".",
+                    "default": "a, b",
+                },
+                "SCHEMA_MAX_BODY": {
+                    "type": ["integer", "string"],
+                    "format": "bytes",
+                    "default": "64MiB",
+                },
+                "SCHEMA_PASSWORD": { "type": "string", "writeOnly": true },
+                "SCHEMA_TOKEN": { "type": "string", "writeOnly": true },
+                "SCHEMA_LOG_FORMAT": {
+                    "type": "string",
+                    "pattern": "^(?:text|[jJ][sS][oO][nN])$",
+                },
+                "SCHEMA_TLS_CERT": {
+                    "type": "string",
+                    "description": "Path to the certificate.",
+                },
+                "SCHEMA_TLS_VERIFY": { "type": "boolean", "default": false },
+            },
+            "required": ["SCHEMA_LISTEN", "SCHEMA_PASSWORD"],
+        })
+    );
+}
+
+#[derive(EnvVar)]
+#[var_name = "SCHEMA_REGION"]
+#[panic_on_invalid]
+#[case(convert = "uppercase")]
+enum Region {
+    Eu,
+    Us,
+}
+
+#[test]
+fn test_enum_schema() {
+    assert_eq!(
+        Region::json_schema(),
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "SCHEMA_REGION": { "type": "string", "enum": ["EU", "US"] },
+            },
+            "required": ["SCHEMA_REGION"],
+        })
+    );
+    assert_eq!(LogFormat::json_schema()["required"], json!([]));
+}
+
+#[allow(dead_code)]
+#[derive(ConfigStruct)]
+struct RegionConfig {
+    #[enumerated]
+    region: Region,
+}
+
+#[allow(dead_code)]
+#[derive(ConfigStruct)]
+struct DefaultedRegionConfig {
+    #[enumerated]
+    #[default("EU")]
+    region: Region,
+}
+
+#[test]
+fn test_enum_field_required() {
+    assert_eq!(
+        RegionConfig::json_schema()["required"],
+        json!(["SCHEMA_REGION"])
+    );
+    assert!(RegionConfig::try_get_from(&HashMap::<&str, &str>::new()).is_err());
+
+    let schema = DefaultedRegionConfig::json_schema();
+    assert_eq!(schema["required"], json!([]));
+    assert_eq!(
+        schema["properties"]["SCHEMA_REGION"]["default"],
+        json!("EU")
+    );
+    let config = DefaultedRegionConfig::try_get_from(&HashMap::<&str, &str>::new());
+    assert!(matches!(
+        config,
+        Ok(DefaultedRegionConfig { region: Region::Eu })
+    ));
+}
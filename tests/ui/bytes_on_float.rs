@@ -0,0 +1,9 @@
+use env_extract::ConfigStruct;
+
+#[derive(ConfigStruct)]
+struct Config {
+    #[bytes]
+    ratio: f64,
+}
+
+fn main() {}
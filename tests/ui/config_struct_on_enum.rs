@@ -0,0 +1,11 @@
+use env_extract::ConfigStruct;
+
+#[derive(ConfigStruct)]
+enum Config {
+    Host,
+}
+
+#[derive(ConfigStruct)]
+struct Tuple(String);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use env_extract::ConfigStruct;
+
+#[derive(ConfigStruct)]
+struct Config {
+    #[var_name(primary = "DB_URL", deprecated)]
+    db_url: String,
+}
+
+fn main() {}
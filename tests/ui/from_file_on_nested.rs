@@ -0,0 +1,15 @@
+use env_extract::ConfigStruct;
+
+#[derive(ConfigStruct)]
+struct Database {
+    host: String,
+}
+
+#[derive(ConfigStruct)]
+struct Config {
+    #[nested]
+    #[from_file]
+    db: Database,
+}
+
+fn main() {}
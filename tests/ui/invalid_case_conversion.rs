@@ -0,0 +1,11 @@
+use env_extract::EnvVar;
+
+#[derive(EnvVar)]
+#[case(convert = "camelcase")]
+enum LogLevel {
+    Error,
+    Info,
+    Invalid,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use std::time::Duration;
+
+use env_extract::ConfigStruct;
+
+#[derive(ConfigStruct)]
+struct Config {
+    #[duration(unit = "weeks")]
+    timeout: Duration,
+}
+
+fn main() {}
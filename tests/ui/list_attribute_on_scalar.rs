@@ -0,0 +1,9 @@
+use env_extract::ConfigStruct;
+
+#[derive(ConfigStruct)]
+struct Config {
+    #[list(separator = ";")]
+    host: String,
+}
+
+fn main() {}
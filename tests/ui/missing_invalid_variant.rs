@@ -0,0 +1,9 @@
+use env_extract::EnvVar;
+
+#[derive(EnvVar)]
+enum LogLevel {
+    Error,
+    Info,
+}
+
+fn main() {}
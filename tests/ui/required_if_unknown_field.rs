@@ -0,0 +1,10 @@
+use env_extract::ConfigStruct;
+
+#[derive(ConfigStruct)]
+struct Config {
+    use_tls: bool,
+    #[required_if(use_ssl = true)]
+    tls_cert: Option<String>,
+}
+
+fn main() {}
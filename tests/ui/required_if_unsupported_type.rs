@@ -0,0 +1,10 @@
+use env_extract::ConfigStruct;
+
+#[derive(ConfigStruct)]
+struct Config {
+    hosts: Vec<String>,
+    #[required_if(hosts = "localhost")]
+    tls_cert: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use env_extract::{ConfigStruct, Secret};
+
+#[derive(ConfigStruct)]
+struct Config {
+    tokens: Secret<std::collections::HashSet<String>>,
+}
+
+fn main() {}
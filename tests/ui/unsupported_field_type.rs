@@ -0,0 +1,9 @@
+use env_extract::ConfigStruct;
+
+#[derive(ConfigStruct)]
+struct Config {
+    host: String,
+    ports: std::collections::BTreeMap<String, u16>,
+}
+
+fn main() {}
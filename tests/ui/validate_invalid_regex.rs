@@ -0,0 +1,9 @@
+use env_extract::ConfigStruct;
+
+#[derive(ConfigStruct)]
+struct Config {
+    #[validate(regex = "^[a-z+$")]
+    name: String,
+}
+
+fn main() {}
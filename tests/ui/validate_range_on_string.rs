@@ -0,0 +1,9 @@
+use env_extract::ConfigStruct;
+
+#[derive(ConfigStruct)]
+struct Config {
+    #[validate(range(min = 1))]
+    name: String,
+}
+
+fn main() {}